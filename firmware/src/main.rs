@@ -28,7 +28,7 @@ mod app {
         LineBuf, framebuf::{FrameBuf, MainFrameBuf, SubFrameBuf},
     };
     use rtt_target::{rtt_init_print, rprintln, rprint};
-    use walkclock::Clock;
+    use walkclock::{Clock, Event};
 
     /// Double-buffered RGB888 frame buffers for main HUB75E display.
     static mut MFBUFS: [MainFrameBuf; 2] = [FrameBuf([[[0u8; 3]; 64]; 64]); 2];
@@ -121,8 +121,7 @@ mod app {
         rprintln!("OK");
 
         rprint!("  Switches... ");
-        // Repeat after the first second and then four times per second thereafter.
-        let switches = Switches::new(pins.switches, 20, 5);
+        let switches = Switches::new(pins.switches);
         rprintln!("OK");
 
         rprint!("  RTC...      ");
@@ -194,6 +193,9 @@ mod app {
         let mut settings = [0u32; 32];
         rtc.read_backup(&mut settings[..]);
         clock.deserialise(&settings[..]);
+        if clock.invalid_settings() > 0 {
+            rprint!("{} invalid settings reset to defaults, ", clock.invalid_settings());
+        }
         rprintln!("OK");
 
         rprint!("  LCD...      ");
@@ -338,25 +340,7 @@ mod app {
         cx.local.tick_tim.clear_uif();
 
         // Process button inputs.
-        cx.local.switches.update();
-        if cx.local.switches.back() {
-            cx.local.clock.key_back();
-        }
-        if cx.local.switches.qr() {
-            cx.local.clock.key_qr();
-        }
-        if cx.local.switches.display() {
-            cx.local.clock.key_display();
-        }
-        if cx.local.switches.enter() {
-            cx.local.clock.key_enter();
-        }
-        if cx.local.switches.left() {
-            cx.local.clock.key_left();
-        }
-        if cx.local.switches.right() {
-            cx.local.clock.key_right();
-        }
+        cx.local.clock.update_keys(cx.local.switches.keys());
 
         if cx.local.clock.use_gps_time() {
             // Process any newly received GNSS times.
@@ -411,8 +395,20 @@ mod app {
 
         // Update the application with the latest date and time from the RTC.
         let time = cx.local.rtc.read();
-        cx.local.clock.set_time(
+        let result = cx.local.clock.set_time(
             time.year as u16 + 2000, time.month, time.day, time.hour, time.minute, time.second);
+        if let Err(e) = result {
+            rprintln!("Invalid RTC time {:?}, {} rejected", e, cx.local.clock.rejected_times());
+        }
+
+        // Handle any events raised by the application, such as alarms.
+        // There is no buzzer fitted, so for now alarm events are just logged.
+        while let Some(event) = cx.local.clock.poll_event() {
+            rprintln!("Clock event: {:?}", event);
+            if event == Event::RestartGPS {
+                cx.shared.ublox.lock(|ublox| ublox.setup());
+            }
+        }
 
         // At the middle of each hour, process potential RTC calibration.
         if time.minute == 30 {
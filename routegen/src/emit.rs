@@ -0,0 +1,69 @@
+use std::io::{self, Write};
+use crate::route::Route;
+
+/// Longest line written, matching the route modules in the firmware.
+const WIDTH: usize = 100;
+
+/// Write the route's tables as Rust statics, ready to be used by a `RouteData`.
+///
+/// Writes `ROUTE` and `DAYS`, then for each landmark layer a table of its
+/// pixels and a table counting how many have been reached each day. Layers are
+/// named after their waypoint type, so type `cathedral` gives `CATHEDRALS` and
+/// `N_CATHEDRALS`, and type `church` gives `CHURCHES` and `N_CHURCHES`.
+pub fn write_tables<W: Write>(mut w: W, route: &Route) -> io::Result<()> {
+    write_pixels(&mut w, "ROUTE", &route.pixels)?;
+    writeln!(w)?;
+    write_numbers(&mut w, "DAYS", "u16", &route.days)?;
+    for layer in route.layers.iter() {
+        let name = layer_name(&layer.kind);
+        writeln!(w)?;
+        write_pixels(&mut w, &name, &layer.points)?;
+        writeln!(w)?;
+        write_numbers(&mut w, &format!("N_{}", name), "u8", &layer.per_day)?;
+    }
+    Ok(())
+}
+
+/// Convert a waypoint type into a plural upper-case identifier.
+pub fn layer_name(kind: &str) -> String {
+    let mut name: String = kind.trim().chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    if name.is_empty() {
+        name.push_str("LANDMARK");
+    }
+    if name.ends_with("CH") || name.ends_with("SH") || name.ends_with('X') {
+        name.push_str("ES");
+    } else if !name.ends_with('S') {
+        name.push('S');
+    }
+    name
+}
+
+fn write_pixels<W: Write>(w: &mut W, name: &str, pixels: &[(u8, u8)]) -> io::Result<()> {
+    writeln!(w, "static {}: [(u8, u8); {}] = [", name, pixels.len())?;
+    for line in pixels.chunks(8) {
+        let line: Vec<String> = line.iter().map(|(x, y)| format!("({}, {})", x, y)).collect();
+        writeln!(w, "    {},", line.join(", "))?;
+    }
+    writeln!(w, "];")
+}
+
+fn write_numbers<W: Write, T: ToString>(
+    w: &mut W, name: &str, ty: &str, numbers: &[T]
+) -> io::Result<()> {
+    writeln!(w, "static {}: [{}; {}] = [", name, ty, numbers.len())?;
+    let mut line = String::from("   ");
+    for n in numbers.iter() {
+        let n = n.to_string();
+        if line.len() + n.len() + 2 > WIDTH {
+            writeln!(w, "{}", line)?;
+            line = String::from("   ");
+        }
+        line.push(' ');
+        line.push_str(&n);
+        line.push(',');
+    }
+    writeln!(w, "{}", line)?;
+    writeln!(w, "];")
+}
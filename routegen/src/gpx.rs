@@ -0,0 +1,127 @@
+use crate::route::BoundingBox;
+
+/// A latitude and longitude in degrees.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LatLon {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+/// A GPX waypoint, used to mark a landmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Waypoint {
+    pub position: LatLon,
+    pub name: String,
+    /// Contents of the `<type>` element, which selects the landmark layer.
+    pub kind: String,
+}
+
+/// The parts of a GPX file used to build a route.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gpx {
+    /// Bounding box from the metadata `<bounds>` element, if present.
+    pub bounds: Option<BoundingBox>,
+    /// Track points walked on each day, in order.
+    pub days: Vec<Vec<LatLon>>,
+    pub waypoints: Vec<Waypoint>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Xml(roxmltree::Error),
+    MissingAttribute(&'static str),
+    InvalidCoordinate(String),
+    NoTracks,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Xml(e) => write!(f, "XML error: {}", e),
+            Error::MissingAttribute(a) => write!(f, "point missing '{}' attribute", a),
+            Error::InvalidCoordinate(c) => write!(f, "invalid coordinate '{}'", c),
+            Error::NoTracks => write!(f, "no tracks found"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<roxmltree::Error> for Error {
+    fn from(e: roxmltree::Error) -> Self {
+        Error::Xml(e)
+    }
+}
+
+impl Gpx {
+    /// Parse a GPX document.
+    ///
+    /// If the document contains several tracks, each track is one day and its
+    /// segments are joined together. If it contains a single track, each of its
+    /// segments is one day.
+    pub fn parse(text: &str) -> Result<Gpx, Error> {
+        let doc = roxmltree::Document::parse(text)?;
+        let root = doc.root_element();
+
+        let tracks: Vec<_> = root.children().filter(|n| n.has_tag_name("trk")).collect();
+        let mut days = Vec::new();
+        if tracks.len() == 1 {
+            for seg in tracks[0].children().filter(|n| n.has_tag_name("trkseg")) {
+                days.push(points(seg, "trkpt")?);
+            }
+        } else {
+            for trk in tracks.iter() {
+                let mut day = Vec::new();
+                for seg in trk.children().filter(|n| n.has_tag_name("trkseg")) {
+                    day.extend(points(seg, "trkpt")?);
+                }
+                days.push(day);
+            }
+        }
+        if days.iter().all(|d| d.is_empty()) {
+            return Err(Error::NoTracks);
+        }
+
+        let mut waypoints = Vec::new();
+        for wpt in root.children().filter(|n| n.has_tag_name("wpt")) {
+            waypoints.push(Waypoint {
+                position: latlon(wpt)?,
+                name: child_text(wpt, "name"),
+                kind: child_text(wpt, "type"),
+            });
+        }
+
+        let bounds = match root.descendants().find(|n| n.has_tag_name("bounds")) {
+            Some(b) => Some(BoundingBox {
+                west: coordinate(b, "minlon")?,
+                south: coordinate(b, "minlat")?,
+                east: coordinate(b, "maxlon")?,
+                north: coordinate(b, "maxlat")?,
+            }),
+            None => None,
+        };
+
+        Ok(Gpx { bounds, days, waypoints })
+    }
+}
+
+fn points(parent: roxmltree::Node, tag: &str) -> Result<Vec<LatLon>, Error> {
+    parent.children().filter(|n| n.has_tag_name(tag)).map(latlon).collect()
+}
+
+fn latlon(node: roxmltree::Node) -> Result<LatLon, Error> {
+    Ok(LatLon { lat: coordinate(node, "lat")?, lon: coordinate(node, "lon")? })
+}
+
+fn coordinate(node: roxmltree::Node, attr: &'static str) -> Result<f64, Error> {
+    let value = node.attribute(attr).ok_or(Error::MissingAttribute(attr))?;
+    value.trim().parse().map_err(|_| Error::InvalidCoordinate(value.to_string()))
+}
+
+fn child_text(node: roxmltree::Node, tag: &str) -> String {
+    node.children()
+        .find(|n| n.has_tag_name(tag))
+        .and_then(|n| n.text())
+        .map(|t| t.trim().to_string())
+        .unwrap_or_default()
+}
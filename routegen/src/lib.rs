@@ -0,0 +1,15 @@
+//! Compile GPX tracks and waypoints into route tables for the walkclock.
+//!
+//! Each day of the walk is one GPX track, or one segment of a single track.
+//! Tracks are projected onto the 64x64 display using a bounding box, and
+//! rasterised into a contiguous path of pixels which never repeats a pixel.
+//!
+//! Waypoints become landmarks, grouped into layers by their `<type>`, and are
+//! snapped onto the nearest pixel of the route.
+
+pub mod emit;
+pub mod gpx;
+pub mod route;
+
+pub use gpx::Gpx;
+pub use route::{BoundingBox, Route};
@@ -0,0 +1,68 @@
+//! Compile a GPX file into walkclock route tables.
+//!
+//! Usage: `routegen [--bbox <west>,<south>,<east>,<north>] <route.gpx>`
+//!
+//! The bounding box defaults to the `<bounds>` element in the GPX metadata.
+//! The tables are written to stdout, and any warnings to stderr.
+
+use std::process::exit;
+use walkclock_routegen::{emit, BoundingBox, Gpx, Route};
+
+fn usage() -> ! {
+    eprintln!("Usage: routegen [--bbox <west>,<south>,<east>,<north>] <route.gpx>");
+    exit(1);
+}
+
+fn main() {
+    let mut bbox = None;
+    let mut path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bbox" => match args.next().map(|b| b.parse::<BoundingBox>()) {
+                Some(Ok(b)) => bbox = Some(b),
+                Some(Err(e)) => {
+                    eprintln!("{}", e);
+                    exit(1);
+                }
+                None => usage(),
+            },
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => usage(),
+        }
+    }
+    let path = match path {
+        Some(path) => path,
+        None => usage(),
+    };
+
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("Error reading {}: {}", path, e);
+        exit(1);
+    });
+    let gpx = Gpx::parse(&text).unwrap_or_else(|e| {
+        eprintln!("Error parsing {}: {}", path, e);
+        exit(1);
+    });
+
+    let bbox = match bbox.or(gpx.bounds) {
+        Some(bbox) => bbox,
+        None => {
+            eprintln!("No bounding box given and {} has no <bounds>", path);
+            exit(1);
+        }
+    };
+
+    let route = Route::compile(&gpx, &bbox).unwrap_or_else(|e| {
+        eprintln!("Error compiling {}: {}", path, e);
+        exit(1);
+    });
+    for warning in route.warnings.iter() {
+        eprintln!("WARNING: {}", warning);
+    }
+    eprintln!("{} days, {} pixels, {} landmark layers.",
+              gpx.days.len(), route.pixels.len(), route.layers.len());
+
+    let stdout = std::io::stdout();
+    emit::write_tables(stdout.lock(), &route).expect("Error writing tables");
+}
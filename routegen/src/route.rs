@@ -0,0 +1,384 @@
+use crate::gpx::{Gpx, LatLon};
+
+/// Size of the display in pixels along each side.
+pub const SIZE: i32 = 64;
+
+/// Region of the world which is projected onto the display, in degrees.
+///
+/// The region is stretched to fill the whole display, so choose its aspect
+/// ratio to suit the latitude, and leave margins clear of the date, time,
+/// and day counters.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub west: f64,
+    pub south: f64,
+    pub east: f64,
+    pub north: f64,
+}
+
+impl std::str::FromStr for BoundingBox {
+    type Err = String;
+
+    /// Parse a bounding box from `west,south,east,north`.
+    fn from_str(s: &str) -> Result<Self, String> {
+        let parts: Vec<f64> = s.split(',')
+            .map(|p| p.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("invalid bounding box '{}': {}", s, e))?;
+        match parts[..] {
+            [west, south, east, north] if west < east && south < north =>
+                Ok(BoundingBox { west, south, east, north }),
+            _ => Err(format!("bounding box '{}' must be west,south,east,north", s)),
+        }
+    }
+}
+
+impl BoundingBox {
+    /// Project a position to its pixel, returning `None` if it is off the display.
+    pub fn project(&self, p: LatLon) -> Option<(i32, i32)> {
+        let x = ((p.lon - self.west) / (self.east - self.west) * SIZE as f64).floor() as i32;
+        let y = ((self.north - p.lat) / (self.north - self.south) * SIZE as f64).floor() as i32;
+        if (0..SIZE).contains(&x) && (0..SIZE).contains(&y) {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+}
+
+/// Errors from compiling a route which would not fit in the route tables.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// A pixel does not fit in the `(u8, u8)` route table.
+    PixelOutOfRange(i32, i32),
+    /// The route has more pixels than a `u16` day index can reach.
+    TooManyPixels(usize),
+    /// A layer has more landmarks than its `u8` per-day counts can hold.
+    TooManyLandmarks(String, usize),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::PixelOutOfRange(x, y) => write!(f, "pixel {},{} is off the display", x, y),
+            Error::TooManyPixels(n) => write!(f, "route has {} pixels, more than a u16 index", n),
+            Error::TooManyLandmarks(kind, n) =>
+                write!(f, "{} '{}' landmarks, more than a u8 count", n, kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A layer of landmarks, made from all waypoints of the same type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    pub kind: String,
+    /// Landmark pixels, in the order they are reached along the route.
+    pub points: Vec<(u8, u8)>,
+    /// Number of landmarks reached by the end of each day.
+    pub per_day: Vec<u8>,
+}
+
+/// A rasterised route, ready to be written out as tables.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Route {
+    /// Every pixel of the route, in the order they are walked.
+    pub pixels: Vec<(u8, u8)>,
+    /// Index into `pixels` of the end of each day.
+    ///
+    /// Day 0 is before walking starts and the final day is a rest day,
+    /// so there are two more entries than days in the GPX.
+    pub days: Vec<u16>,
+    pub layers: Vec<Layer>,
+    /// Problems found while compiling, such as points outside the bounding box.
+    pub warnings: Vec<String>,
+}
+
+impl Route {
+    /// Compile the tracks and waypoints in `gpx` into a route.
+    ///
+    /// Fails if the route would not fit in the tables' integer types.
+    pub fn compile(gpx: &Gpx, bbox: &BoundingBox) -> Result<Route, Error> {
+        let mut route = Route { days: vec![0], ..Default::default() };
+
+        let mut prev: Option<(i32, i32)> = None;
+        for (day, points) in gpx.days.iter().enumerate() {
+            for p in points.iter() {
+                let px = match bbox.project(*p) {
+                    Some(px) => px,
+                    None => {
+                        route.warnings.push(format!(
+                            "day {} point {:.5},{:.5} is outside the bounding box",
+                            day + 1, p.lat, p.lon));
+                        continue;
+                    }
+                };
+                match prev {
+                    Some(from) => for px in line(from, px) { route.push(px)? },
+                    None => route.push(px)?,
+                }
+                prev = Some(px);
+            }
+            route.days.push(route.end()?);
+        }
+        route.days.push(route.end()?);
+
+        for wpt in gpx.waypoints.iter() {
+            route.add_landmark(bbox, wpt.position, &wpt.name, &wpt.kind);
+        }
+        for layer in route.layers.iter_mut() {
+            let too_many = |_| Error::TooManyLandmarks(layer.kind.clone(), layer.points.len());
+            layer.per_day = route.days.iter().map(|&end| {
+                let count = layer.points.iter()
+                    .filter(|p| route.pixels[..end as usize].contains(p))
+                    .count();
+                u8::try_from(count).map_err(too_many)
+            }).collect::<Result<_, _>>()?;
+        }
+
+        Ok(route)
+    }
+
+    /// Index of the end of the route so far.
+    fn end(&self) -> Result<u16, Error> {
+        u16::try_from(self.pixels.len()).map_err(|_| Error::TooManyPixels(self.pixels.len()))
+    }
+
+    /// Append a pixel to the route unless it has already been visited.
+    ///
+    /// Skipping a visited pixel where the route crosses or retraces itself can leave
+    /// the next new pixel out of reach of the last one, which is reported as a warning.
+    fn push(&mut self, (x, y): (i32, i32)) -> Result<(), Error> {
+        let px = match (u8::try_from(x), u8::try_from(y)) {
+            (Ok(px), Ok(py)) => (px, py),
+            _ => return Err(Error::PixelOutOfRange(x, y)),
+        };
+        if self.pixels.contains(&px) {
+            return Ok(());
+        }
+        if let Some(&(lx, ly)) = self.pixels.last() {
+            if (lx as i32 - x).abs() > 1 || (ly as i32 - y).abs() > 1 {
+                self.warnings.push(format!(
+                    "route jumps from {},{} to {},{} after revisiting pixels", lx, ly, x, y));
+            }
+        }
+        self.pixels.push(px);
+        Ok(())
+    }
+
+    /// Snap a landmark onto the closest route pixel and insert it into its layer.
+    fn add_landmark(&mut self, bbox: &BoundingBox, position: LatLon, name: &str, kind: &str) {
+        let (x, y) = match bbox.project(position) {
+            Some(px) => px,
+            None => {
+                self.warnings.push(format!("landmark '{}' is outside the bounding box", name));
+                return;
+            }
+        };
+        let dist = |&(px, py): &(u8, u8)| (px as i32 - x).pow(2) + (py as i32 - y).pow(2);
+        let idx = match (0..self.pixels.len()).min_by_key(|&i| dist(&self.pixels[i])) {
+            Some(idx) => idx,
+            None => return,
+        };
+        if dist(&self.pixels[idx]) > 2 * 2 {
+            self.warnings.push(format!("landmark '{}' is far from the route", name));
+        }
+
+        let layer = match self.layers.iter().position(|l| l.kind == kind) {
+            Some(layer) => &mut self.layers[layer],
+            None => {
+                self.layers.push(Layer {
+                    kind: kind.to_string(), points: Vec::new(), per_day: Vec::new(),
+                });
+                self.layers.last_mut().unwrap()
+            }
+        };
+        let px = self.pixels[idx];
+        if layer.points.contains(&px) {
+            self.warnings.push(format!("landmark '{}' shares a pixel with another", name));
+            return;
+        }
+        let pixels = &self.pixels;
+        let pos = layer.points.iter()
+            .position(|p| pixels.iter().position(|q| q == p).unwrap() > idx)
+            .unwrap_or(layer.points.len());
+        layer.points.insert(pos, px);
+    }
+}
+
+/// Pixels on the line from `from` to `to`, excluding `from` and including `to`.
+///
+/// Consecutive pixels are always adjacent, including diagonally.
+fn line(from: (i32, i32), to: (i32, i32)) -> Vec<(i32, i32)> {
+    let (mut x, mut y) = from;
+    let dx = (to.0 - x).abs();
+    let dy = -(to.1 - y).abs();
+    let sx = if x < to.0 { 1 } else { -1 };
+    let sy = if y < to.1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    let mut pixels = Vec::new();
+    while (x, y) != to {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+        pixels.push((x, y));
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpx::Waypoint;
+
+    /// One degree per pixel, so pixel (x, y) covers longitude x and latitude 63 - y.
+    const BBOX: BoundingBox = BoundingBox { west: 0.0, south: 0.0, east: 64.0, north: 64.0 };
+
+    /// Position in the middle of pixel (x, y).
+    fn at(x: i32, y: i32) -> LatLon {
+        LatLon { lat: 63.5 - y as f64, lon: x as f64 + 0.5 }
+    }
+
+    fn waypoint(x: i32, y: i32, name: &str, kind: &str) -> Waypoint {
+        Waypoint { position: at(x, y), name: name.to_string(), kind: kind.to_string() }
+    }
+
+    fn adjacent(a: (i32, i32), b: (i32, i32)) -> bool {
+        a != b && (a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1
+    }
+
+    #[test]
+    fn project() {
+        assert_eq!(BBOX.project(at(0, 0)), Some((0, 0)));
+        assert_eq!(BBOX.project(at(63, 63)), Some((63, 63)));
+        assert_eq!(BBOX.project(at(12, 34)), Some((12, 34)));
+        assert_eq!(BBOX.project(LatLon { lat: 64.0, lon: 0.0 }), Some((0, 0)));
+        assert_eq!(BBOX.project(LatLon { lat: 32.0, lon: 64.0 }), None);
+        assert_eq!(BBOX.project(LatLon { lat: 0.0, lon: 32.0 }), None);
+        assert_eq!(BBOX.project(LatLon { lat: 32.0, lon: -0.1 }), None);
+        assert_eq!(BBOX.project(LatLon { lat: 64.1, lon: 32.0 }), None);
+    }
+
+    #[test]
+    fn line_is_contiguous() {
+        assert_eq!(line((3, 3), (3, 3)), vec![]);
+        assert_eq!(line((0, 0), (3, 0)), vec![(1, 0), (2, 0), (3, 0)]);
+        assert_eq!(line((2, 2), (0, 0)), vec![(1, 1), (0, 0)]);
+        for &(from, to) in [((0, 0), (5, 2)), ((10, 3), (1, 9)), ((7, 7), (7, 0))].iter() {
+            let pixels = line(from, to);
+            assert_eq!(pixels.last(), Some(&to));
+            assert!(!pixels.contains(&from));
+            assert!(adjacent(from, pixels[0]));
+            assert!(pixels.windows(2).all(|w| adjacent(w[0], w[1])));
+        }
+    }
+
+    #[test]
+    fn push_skips_visited_pixels() {
+        let mut route = Route::default();
+        for &px in [(1, 1), (2, 1), (1, 1), (2, 2)].iter() {
+            route.push(px).unwrap();
+        }
+        assert_eq!(route.pixels, vec![(1, 1), (2, 1), (2, 2)]);
+        assert!(route.warnings.is_empty());
+    }
+
+    #[test]
+    fn push_rejects_pixels_out_of_range() {
+        let mut route = Route::default();
+        assert_eq!(route.push((256, 0)), Err(Error::PixelOutOfRange(256, 0)));
+        assert_eq!(route.push((0, -1)), Err(Error::PixelOutOfRange(0, -1)));
+        assert!(route.pixels.is_empty());
+    }
+
+    #[test]
+    fn push_warns_when_route_jumps() {
+        let mut route = Route::default();
+        for &px in [(5, 4), (5, 5), (6, 5), (5, 5), (5, 6)].iter() {
+            route.push(px).unwrap();
+        }
+        assert!(route.warnings.is_empty());
+
+        // Crossing straight over a visited pixel leaves a gap.
+        let mut route = Route::default();
+        for &px in [(4, 5), (5, 5), (6, 5), (5, 4), (5, 5), (5, 6)].iter() {
+            route.push(px).unwrap();
+        }
+        assert_eq!(route.pixels, vec![(4, 5), (5, 5), (6, 5), (5, 4), (5, 6)]);
+        assert_eq!(route.warnings.len(), 1);
+    }
+
+    #[test]
+    fn compile_days() {
+        let gpx = Gpx {
+            days: vec![vec![at(0, 0), at(3, 0)], vec![at(3, 2)]],
+            ..Default::default()
+        };
+        let route = Route::compile(&gpx, &BBOX).unwrap();
+        assert_eq!(route.pixels, vec![(0, 0), (1, 0), (2, 0), (3, 0), (3, 1), (3, 2)]);
+        assert_eq!(route.days, vec![0, 4, 6, 6]);
+        assert!(route.warnings.is_empty());
+    }
+
+    #[test]
+    fn compile_warns_at_self_crossing() {
+        let gpx = Gpx {
+            days: vec![vec![at(0, 5), at(10, 5), at(10, 0), at(5, 0), at(5, 10)]],
+            ..Default::default()
+        };
+        let route = Route::compile(&gpx, &BBOX).unwrap();
+        assert!(route.warnings.iter().any(|w| w.contains("jumps from 5,4 to 5,6")));
+    }
+
+    #[test]
+    fn landmarks_snap_to_route() {
+        let gpx = Gpx {
+            days: vec![vec![at(0, 10), at(9, 10)], vec![at(20, 10)]],
+            waypoints: vec![
+                waypoint(15, 11, "Second", "cathedral"),
+                waypoint(3, 9, "First", "cathedral"),
+                waypoint(9, 10, "Refuge", "refuge"),
+                waypoint(40, 40, "Far away", "refuge"),
+                waypoint(3, 11, "Same pixel", "cathedral"),
+            ],
+            ..Default::default()
+        };
+        let route = Route::compile(&gpx, &BBOX).unwrap();
+
+        assert_eq!(route.layers.len(), 2);
+        let cathedrals = &route.layers[0];
+        assert_eq!(cathedrals.kind, "cathedral");
+        assert_eq!(cathedrals.points, vec![(3, 10), (15, 10)]);
+        assert_eq!(cathedrals.per_day, vec![0, 1, 2, 2]);
+
+        let refuges = &route.layers[1];
+        assert_eq!(refuges.points, vec![(9, 10), (20, 10)]);
+        assert_eq!(refuges.per_day, vec![0, 1, 2, 2]);
+
+        assert!(route.warnings.iter().any(|w| w.contains("'Far away' is far from the route")));
+        assert!(route.warnings.iter().any(|w| w.contains("'Same pixel' shares a pixel")));
+    }
+
+    #[test]
+    fn landmarks_outside_bounding_box() {
+        let gpx = Gpx {
+            days: vec![vec![at(0, 0), at(3, 0)]],
+            waypoints: vec![Waypoint {
+                position: LatLon { lat: 100.0, lon: 0.0 },
+                name: "Off map".to_string(),
+                kind: "refuge".to_string(),
+            }],
+            ..Default::default()
+        };
+        let route = Route::compile(&gpx, &BBOX).unwrap();
+        assert!(route.layers.is_empty());
+        assert!(route.warnings.iter().any(|w| w.contains("'Off map' is outside")));
+    }
+}
@@ -5,7 +5,7 @@ use embedded_graphics::{prelude::*, pixelcolor::Rgb888};
 use embedded_graphics_simulator::{
     OutputSettings, SimulatorDisplay, Window, SimulatorEvent, sdl2::Keycode,
 };
-use walkclock::Clock;
+use walkclock::{Clock, Key, Keys};
 
 pub fn main() {
     let mut main_display = SimulatorDisplay::new(Size::new(64, 64));
@@ -27,9 +27,13 @@ pub fn main() {
             *word = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
         }
         clock.deserialise(&settings[..]);
+        if clock.invalid_settings() > 0 {
+            println!("{} invalid settings reset to defaults", clock.invalid_settings());
+        }
     }
 
     let mut wallclock: OffsetDateTime = OffsetDateTime::now_utc();
+    let mut keys = Keys::new();
 
     'outer: loop {
         let t0 = std::time::Instant::now();
@@ -37,10 +41,13 @@ pub fn main() {
         if clock.use_gps_time() {
             wallclock = OffsetDateTime::now_utc()
         }
-        clock.set_time(
+        let result = clock.set_time(
             wallclock.year() as u16, wallclock.month() as u8, wallclock.day() as u8,
             wallclock.hour() as u8, wallclock.minute() as u8, wallclock.second() as u8,
         );
+        if let Err(e) = result {
+            println!("Invalid time {:?}, {} rejected", e, clock.rejected_times());
+        }
 
         if let Some(jpeg) = clock.prerender_jpeg() {
             let mut decoder = jpeg_decoder::Decoder::new(jpeg);
@@ -67,28 +74,21 @@ pub fn main() {
         for event in main_window.events() {
             match event {
                 SimulatorEvent::Quit => break 'outer,
-                SimulatorEvent::KeyDown { keycode, .. } => match keycode {
-                        Keycode::Escape => break 'outer,
-
-                        Keycode::Q => clock.key_back(),
-                        Keycode::W => clock.key_qr(),
-                        Keycode::E => clock.key_display(),
-                        Keycode::A => clock.key_enter(),
-                        Keycode::S => clock.key_left(),
-                        Keycode::D => clock.key_right(),
-
-                        Keycode::Return => clock.key_enter(),
-                        Keycode::Backspace => clock.key_back(),
-                        Keycode::Up => clock.key_back(),
-                        Keycode::Down => clock.key_enter(),
-                        Keycode::Left => clock.key_left(),
-                        Keycode::Right => clock.key_right(),
-
-                        _ => (),
+                SimulatorEvent::KeyDown { keycode: Keycode::Escape, .. } => break 'outer,
+                SimulatorEvent::KeyDown { keycode, .. } => if let Some(key) = key(keycode) {
+                    keys = keys.with(key, true);
+                },
+                SimulatorEvent::KeyUp { keycode, .. } => if let Some(key) = key(keycode) {
+                    keys = keys.with(key, false);
                 },
                 _ => (),
             }
         }
+        clock.update_keys(keys);
+
+        while let Some(event) = clock.poll_event() {
+            println!("Clock event: {:?}", event);
+        }
 
         if clock.time_changed() {
             let (year, month, day, hour, minute, second) = clock.menu_time();
@@ -115,3 +115,24 @@ pub fn main() {
         wallclock += Duration::milliseconds(50);
     }
 }
+
+/// Map keyboard keys to clock keys.
+fn key(keycode: Keycode) -> Option<Key> {
+    match keycode {
+        Keycode::Q => Some(Key::Back),
+        Keycode::W => Some(Key::Qr),
+        Keycode::E => Some(Key::Display),
+        Keycode::A => Some(Key::Enter),
+        Keycode::S => Some(Key::Left),
+        Keycode::D => Some(Key::Right),
+
+        Keycode::Return => Some(Key::Enter),
+        Keycode::Backspace => Some(Key::Back),
+        Keycode::Up => Some(Key::Back),
+        Keycode::Down => Some(Key::Enter),
+        Keycode::Left => Some(Key::Left),
+        Keycode::Right => Some(Key::Right),
+
+        _ => None,
+    }
+}
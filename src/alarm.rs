@@ -0,0 +1,119 @@
+use time::Weekday;
+use crate::Name;
+
+/// Number of alarms available in the menu.
+pub const N_ALARMS: usize = 3;
+
+/// Menu settings for each alarm: on/off, time, and days.
+pub const ALARM_SETTINGS: [(Name, Name, Name); N_ALARMS] = [
+    (Name::Alarm1, Name::Alarm1Time, Name::Alarm1Days),
+    (Name::Alarm2, Name::Alarm2Time, Name::Alarm2Days),
+    (Name::Alarm3, Name::Alarm3Time, Name::Alarm3Days),
+];
+
+/// Choices of days an alarm may go off on.
+///
+/// New choices should be appended to keep saved indices valid.
+pub const DAY_NAMES: &[Name] = &[
+    Name::EveryDay, Name::Weekdays, Name::Weekends,
+    Name::Monday, Name::Tuesday, Name::Wednesday, Name::Thursday, Name::Friday,
+    Name::Saturday, Name::Sunday,
+];
+
+/// Get the weekday mask for a choice from `DAY_NAMES`, with Monday as bit 0.
+pub fn day_mask(days: Name) -> u8 {
+    match days {
+        Name::EveryDay  => 0b111_1111,
+        Name::Weekdays  => 0b001_1111,
+        Name::Weekends  => 0b110_0000,
+        Name::Monday    => 0b000_0001,
+        Name::Tuesday   => 0b000_0010,
+        Name::Wednesday => 0b000_0100,
+        Name::Thursday  => 0b000_1000,
+        Name::Friday    => 0b001_0000,
+        Name::Saturday  => 0b010_0000,
+        Name::Sunday    => 0b100_0000,
+        _ => 0,
+    }
+}
+
+/// A single enabled alarm.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Alarm {
+    pub hour: u8,
+    pub minute: u8,
+    /// Days the alarm goes off on, with Monday as bit 0.
+    pub days: u8,
+}
+
+impl Alarm {
+    /// Check whether this alarm goes off at `hour`:`minute` on `weekday`.
+    pub fn due(&self, weekday: Weekday, hour: u8, minute: u8) -> bool {
+        let day = 1 << weekday.number_days_from_monday();
+        self.days & day != 0 && self.hour == hour && self.minute == minute
+    }
+}
+
+/// State of the alarm subsystem.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AlarmState {
+    /// No alarm is going off.
+    Idle,
+    /// An alarm is going off.
+    Ringing,
+    /// An alarm was snoozed and will go off again at the given local hour and minute.
+    Snoozed { hour: u8, minute: u8 },
+}
+
+/// Events emitted by `Clock` for the firmware to act on, such as driving a buzzer.
+///
+/// Retrieve events by calling `Clock::poll_event()`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Event {
+    /// An alarm started going off, either for the first time or after a snooze.
+    AlarmRinging,
+    /// The ringing alarm was snoozed.
+    AlarmSnoozed,
+    /// The alarm was dismissed.
+    AlarmDismissed,
+    /// The countdown timer finished.
+    TimerFinished,
+    /// The user asked for the GPS receiver to be restarted from the menu.
+    RestartGPS,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEEK: [Weekday; 7] = [
+        Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday,
+        Weekday::Friday, Weekday::Saturday, Weekday::Sunday,
+    ];
+
+    #[test]
+    fn day_masks() {
+        assert_eq!(day_mask(Name::EveryDay), day_mask(Name::Weekdays) | day_mask(Name::Weekends));
+        assert_eq!(day_mask(Name::Weekdays) & day_mask(Name::Weekends), 0);
+        // The single days follow the order of `WEEK`, and so of `DAY_NAMES`.
+        for (i, &days) in DAY_NAMES[3..].iter().enumerate() {
+            assert_eq!(day_mask(days), 1 << i, "{:?}", days);
+            assert_eq!(WEEK[i].number_days_from_monday(), i as u8);
+        }
+        assert_eq!(day_mask(Name::Alarm1), 0);
+    }
+
+    #[test]
+    fn due_on_chosen_days() {
+        let alarm = Alarm { hour: 6, minute: 45, days: day_mask(Name::Weekdays) };
+        for (i, &weekday) in WEEK.iter().enumerate() {
+            assert_eq!(alarm.due(weekday, 6, 45), i < 5, "{}", weekday);
+        }
+        assert!(!alarm.due(Weekday::Monday, 6, 44));
+        assert!(!alarm.due(Weekday::Monday, 7, 45));
+
+        let sunday = Alarm { days: day_mask(Name::Sunday), ..alarm };
+        assert!(sunday.due(Weekday::Sunday, 6, 45));
+        assert!(!sunday.due(Weekday::Saturday, 6, 45));
+    }
+}
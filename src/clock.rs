@@ -1,19 +1,25 @@
 use core::fmt::Write;
-use time::{PrimitiveDateTime, OffsetDateTime, Date, Time, Month, Duration, UtcOffset};
-use heapless::String;
+use time::{PrimitiveDateTime, Date, Month, Time, UtcOffset};
+use heapless::{String, Deque};
 use embedded_graphics::{
-    image::Image,
-    mono_font::{ascii::FONT_6X9, ascii::FONT_9X18, MonoTextStyle},
+    mono_font::{iso_8859_1::FONT_9X18, MonoTextStyle},
     pixelcolor::Rgb888,
-    Pixel,
     prelude::*,
+    primitives::{Line, PrimitiveStyle},
     text::{Alignment, Baseline, Text, TextStyleBuilder},
 };
 use tinytga::Tga;
-use crate::{Name, map::{Map, MAP_NAMES}, menu::{Menu, Category, Setting}};
-
-/// Default URL for QR code if no specific entry is known.
-static DEFAULT_URL: &str = "HTTPS://TIMGREIG.CO.UK";
+use crate::{Name, map::{Map, Progress}, menu::{self, Menu}};
+use crate::settings::{key, MENU};
+use crate::face::{self, FaceId, FACES};
+use crate::keys::{Key, KeyEvent, Keys, Recogniser};
+use crate::tz::{self, TimeZone};
+use crate::alarm::{self, Alarm, AlarmState, Event, ALARM_SETTINGS};
+use crate::timer::{Countdown, Stopwatch};
+use crate::greetings;
+use crate::lang::{Language, MONTH_NAMES, WEEKDAY_NAMES};
+use crate::format::{DateFormat, Format, TimeFormat};
+use crate::store;
 
 /// Simple date-time representation optimised for grabbing
 /// the time components we need without too much extra storage
@@ -28,12 +34,40 @@ pub struct DateTime {
     second: u8,
 }
 
-impl From<&DateTime> for PrimitiveDateTime {
-    fn from(dt: &DateTime) -> PrimitiveDateTime {
-        let date = Date::from_calendar_date(
-            dt.year as i32, (dt.month as u8).try_into().unwrap(), dt.day as u8).unwrap();
-        let time = Time::from_hms(dt.hour, dt.minute, dt.second).unwrap();
-        PrimitiveDateTime::new(date, time)
+/// Reasons a date and time may be rejected by `Clock::set_time()`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimeError {
+    /// Year is outside 2000 to 2099, the range supported by the menu and RTC.
+    Year,
+    /// Month is outside 1 to 12.
+    Month,
+    /// Day does not exist in the given month.
+    Day,
+    /// Hour is outside 0 to 23.
+    Hour,
+    /// Minute is outside 0 to 59.
+    Minute,
+    /// Second is outside 0 to 59.
+    Second,
+}
+
+impl TryFrom<&DateTime> for PrimitiveDateTime {
+    type Error = TimeError;
+
+    fn try_from(dt: &DateTime) -> Result<PrimitiveDateTime, TimeError> {
+        let month = Month::try_from(dt.month).map_err(|_| TimeError::Month)?;
+        let date = Date::from_calendar_date(dt.year as i32, month, dt.day)
+            .map_err(|_| TimeError::Day)?;
+        if dt.hour > 23 {
+            Err(TimeError::Hour)
+        } else if dt.minute > 59 {
+            Err(TimeError::Minute)
+        } else if dt.second > 59 {
+            Err(TimeError::Second)
+        } else {
+            let time = Time::from_hms(dt.hour, dt.minute, dt.second).map_err(|_| TimeError::Second)?;
+            Ok(PrimitiveDateTime::new(date, time))
+        }
     }
 }
 
@@ -47,22 +81,35 @@ impl From<&PrimitiveDateTime> for DateTime {
 }
 
 impl DateTime {
-    /// Return a three-letter short name for the current month.
-    pub fn month_name_short(&self) -> &'static str {
-        match self.month {
-            1 => "JAN",
-            2 => "FEB",
-            3 => "MAR",
-            4 => "APR",
-            5 => "MAY",
-            6 => "JUN",
-            7 => "JUL",
-            8 => "AUG",
-            9 => "SEP",
-            10 => "OCT",
-            11 => "NOV",
-            12 => "DEC",
-            _ => "",
+    /// Get the start of the minute containing this date and time.
+    fn minute_start(&self) -> DateTime {
+        DateTime { second: 0, ..*self }
+    }
+
+    /// Check this is a valid date and time within the years supported by the clock.
+    pub fn validate(&self) -> Result<(), TimeError> {
+        if !(2000..=2099).contains(&self.year) {
+            return Err(TimeError::Year);
+        }
+        PrimitiveDateTime::try_from(self).map(|_| ())
+    }
+
+    /// Return a three-letter short name for the current month in `language`.
+    pub fn month_name_short(&self, language: Language) -> &'static str {
+        match MONTH_NAMES.get((self.month as usize).wrapping_sub(1)) {
+            Some(name) => name.label(language),
+            None => "",
+        }
+    }
+
+    /// Return a short name for the current day of the week in `language`.
+    pub fn weekday_name_short(&self, language: Language) -> &'static str {
+        match PrimitiveDateTime::try_from(self) {
+            Ok(dt) => {
+                let weekday = dt.weekday().number_days_from_monday() as usize;
+                WEEKDAY_NAMES[weekday].label(language)
+            },
+            Err(_) => "",
         }
     }
 
@@ -91,14 +138,6 @@ impl DateTime {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum DisplayType {
-    Off,
-    Map,
-    Qr,
-    Jpeg,
-}
-
 #[derive(Debug)]
 pub struct Clock {
     utc: DateTime,
@@ -106,55 +145,56 @@ pub struct Clock {
     map: Option<(Map, Tga<'static, Rgb888>)>,
     gps_status: String<17>,
     frame: u16,
-    display_type: DisplayType,
-    text_color: Rgb888,
+    face: Option<FaceId>,
     needs_saving: bool,
     time_set: bool,
-    menu: Menu<3, 9>,
+    start_date: Option<Date>,
+    last_seen: Option<Date>,
+    caught_up: Option<(u16, u8)>,
+    alarm: AlarmState,
+    alarm_rung: Option<DateTime>,
+    events: Deque<Event, 4>,
+    countdown: Countdown,
+    stopwatch: Stopwatch,
+    rejected_times: u32,
+    invalid_settings: usize,
+    test_pattern: bool,
+    keys: Recogniser,
+    display_pressed: bool,
+    zone: TimeZone,
+    menu: ClockMenu,
 }
 
-/// Create the Menu structure used by Clock.
-const fn menu() -> Menu<3, 9> {
-    Menu::new([
-        Category::new(Name::DateTime, [
-            Setting::new_onoff(Name::GPSTime, true, true),
-            Setting::new_numeric(Name::Year, false, 2000, 2099, 2000),
-            Setting::new_numeric(Name::Month, false, 1, 12, 1),
-            Setting::new_numeric(Name::Day, false, 1, 31, 1),
-            Setting::new_numeric(Name::Hour, false, 0, 23, 0),
-            Setting::new_numeric(Name::Minute, false, 0, 59, 0),
-            Setting::new_numeric(Name::Second, false, 0, 59, 0),
-            Setting::new_onoff(Name::AutomaticDST, true, true),
-            Setting::new_numeric(Name::UTCOffset, false, -12, 12, 0),
-        ]),
-        Category::new(Name::Map, [
-            Setting::new_choice(Name::Route, true, 0, MAP_NAMES),
-            Setting::new_numeric(Name::RouteDay, true, 0, 999, 0),
-            Setting::new_onoff(Name::AnimateRoute, true, true),
-            Setting::new_onoff(Name::HourlyImages, true, true),
-            Setting::new_disabled(),
-            Setting::new_disabled(),
-            Setting::new_disabled(),
-            Setting::new_disabled(),
-            Setting::new_disabled(),
-        ]),
-        Category::new(Name::Display, [
-            Setting::new_numeric(Name::Brightness, true, 0, 10, 10),
-            Setting::new_onoff(Name::DimAtNight, true, true),
-            Setting::new_numeric(Name::DimBrightness, true, 0, 10, 8),
-            Setting::new_numeric(Name::DimStartHour, true, 0, 23, 23),
-            Setting::new_numeric(Name::DimEndHour, true, 0, 23, 7),
-            Setting::new_disabled(),
-            Setting::new_disabled(),
-            Setting::new_disabled(),
-            Setting::new_disabled(),
-        ]),
-    ])
-}
+/// Number of settings in the menu.
+pub const N_SETTINGS: usize = menu::count(MENU);
+
+/// Menu type used by Clock.
+pub type ClockMenu = Menu<N_SETTINGS>;
+
+/// Number of seconds to show the number of days caught up after being off.
+const CATCH_UP_SECONDS: u8 = 10;
 
-/// Current version of menu. Increment every time the menu is changed
-/// to ensure stale saved menu settings are not incorrectly applied.
-const MENU_VERSION: u16 = 2;
+/// Largest change of time counted by the timer and stopwatch, in seconds.
+///
+/// Covers a few missed updates; larger jumps, such as the time being set from the
+/// menu or corrected by GPS, are not counted.
+const MAX_TICK_SECONDS: i64 = 60;
+
+/// Current version of the serialised state.
+///
+/// Settings are tagged with stable IDs, so this only needs incrementing if the
+/// layout of the serialised state itself changes. Version 2 saved one word per
+/// setting in menu order, and is migrated by `store::migrate_v2()`.
+const STATE_VERSION: u16 = 3;
+
+/// Default menu settings, which are left out when serialising.
+static DEFAULT_MENU: ClockMenu = Menu::new(MENU);
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Clock {
     /// Create a new Clock instance.
@@ -165,44 +205,95 @@ impl Clock {
             map: None,
             gps_status: String::new(),
             frame: 0,
-            display_type: DisplayType::Map,
-            text_color: Rgb888::WHITE,
+            face: Some(FaceId::Route),
             needs_saving: false,
             time_set: false,
-            menu: menu(),
+            start_date: None,
+            last_seen: None,
+            caught_up: None,
+            alarm: AlarmState::Idle,
+            alarm_rung: None,
+            events: Deque::new(),
+            countdown: Countdown::new(5 * 60),
+            stopwatch: Stopwatch::new(),
+            rejected_times: 0,
+            invalid_settings: 0,
+            test_pattern: false,
+            keys: Recogniser::default(),
+            display_pressed: false,
+            zone: menu_zone(&DEFAULT_MENU),
+            menu: DEFAULT_MENU.clone(),
         }
     }
 
     /// Set the date and time in UTC.
-    pub fn set_time(&mut self, year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) {
+    ///
+    /// Invalid dates and times, such as from a glitchy RTC read or a bad GPS message,
+    /// are rejected with an error, leaving the previous time in place and incrementing
+    /// the count returned by `rejected_times()`.
+    pub fn set_time(&mut self, year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8)
+        -> Result<(), TimeError>
+    {
         let new = DateTime { year, month, day, hour, minute, second };
+        if let Err(e) = new.validate() {
+            self.rejected_times = self.rejected_times.saturating_add(1);
+            return Err(e);
+        }
         if self.utc != new {
-            // Advance map day on day rollover, resetting to 0 at the end.
-            if self.time_set && day != self.utc.day {
-                if self.map_day() == self.map_days() - 1 {
-                    self.set_map_day(0);
-                } else {
-                    self.set_map_day(self.map_day() + 1);
-                }
-                self.needs_saving = true;
-            }
-
             // Set new UTC and recompute new local time.
+            let first = !self.time_set;
+            let prev = self.local;
+            let elapsed = if first { 0 } else { seconds_between(&self.utc, &new) };
             self.utc = new;
             self.local = self.local_time();
+            self.time_set = true;
+
+            // Count the time elapsed on the timer and stopwatch, ignoring jumps in time.
+            let secs = if (0..=MAX_TICK_SECONDS).contains(&elapsed) { elapsed as u32 } else { 0 };
+            if self.countdown.tick(secs) {
+                self.face = Some(FaceId::Timer);
+                self.push_event(Event::TimerFinished);
+            }
+            self.stopwatch.tick(secs);
+
+            // Check alarms whenever the local minute changes.
+            if prev.minute_start() != self.local.minute_start() {
+                self.check_alarms();
+            }
+
+            // Start the walk today if it has not yet been started.
+            if self.start_date.is_none() {
+                self.start_date = self.route_date();
+                self.needs_saving = true;
+            }
+
+            // The route day follows from the start date, so days missed while switched
+            // off are caught up automatically, but count them to show a notice.
+            self.caught_up = self.caught_up
+                .and_then(|(days, secs)| secs.checked_sub(1).map(|secs| (days, secs)));
+            if let Some(today) = self.route_date() {
+                if let (true, Some(last)) = (first, self.last_seen) {
+                    let missed = today.to_julian_day() - last.to_julian_day();
+                    if missed > 0 {
+                        self.caught_up = Some((missed.min(u16::MAX as i32) as u16, CATCH_UP_SECONDS));
+                    }
+                }
+                if self.last_seen != Some(today) {
+                    self.last_seen = Some(today);
+                    self.needs_saving = true;
+                }
+            }
 
             // Update menu entries for new time.
-            let date = self.menu.category_mut(Name::DateTime).unwrap();
-            date.setting_set_numeric(Name::Year, year as i16);
-            date.setting_set_numeric(Name::Month, month as i16);
-            date.setting_set_numeric(Name::Day, day as i16);
-            date.setting_set_numeric(Name::Hour, hour as i16);
-            date.setting_set_numeric(Name::Minute, minute as i16);
-            date.setting_set_numeric(Name::Second, second as i16);
-
-            // Record that the time has been set at least once.
-            self.time_set = true;
+            self.menu.setting_set_date(Name::Date, year, month, day);
+            self.menu.setting_set_time(Name::Time, hour, minute);
         }
+        Ok(())
+    }
+
+    /// Get the number of times `set_time()` has rejected an invalid date or time.
+    pub fn rejected_times(&self) -> u32 {
+        self.rejected_times
     }
 
     /// Set GPS status string to "GPS: Error"
@@ -255,17 +346,13 @@ impl Clock {
         where D: DrawTarget<Color = Rgb888>
     {
         self.frame = self.frame.wrapping_add(1);
-        match self.display_type {
-            DisplayType::Off => (),
-            DisplayType::Qr => self.render_main_qr(display),
-            DisplayType::Jpeg => self.render_main_datetime(display, self.text_color),
-            DisplayType::Map => {
-                if self.jpeg_override() {
-                    self.render_main_datetime(display, self.text_color);
-                } else {
-                    self.render_main_clock(display);
-                }
-            }
+        if self.test_pattern {
+            face::render_test_pattern(display);
+        } else if let Some(face) = self.face {
+            face.render(&self.face_state(), display);
+        } else if self.alarm == AlarmState::Ringing {
+            // Show the time while an alarm rings even if the display was turned off.
+            FaceId::BigClock.render(&self.face_state(), display);
         }
     }
 
@@ -280,47 +367,185 @@ impl Clock {
         }
     }
 
+    /// Call once per frame, 20 times a second, with the keys currently held.
+    ///
+    /// Recognises presses, long presses, repeats, and chords, and handles them
+    /// using `key_event()`. Closes the menu after the menu timeout without any keys held.
+    pub fn update_keys(&mut self, keys: Keys) {
+        for event in self.keys.update(keys) {
+            self.key_event(event);
+        }
+        if keys.is_empty() {
+            let timeout = self.menu.get(key::MenuTimeout) as u16;
+            if self.menu.idle(timeout * 20) {
+                self.process_menu_update();
+            }
+        } else {
+            self.menu.touch();
+        }
+    }
+
+    /// Call when a key gesture is recognised.
+    ///
+    /// Presses are handled as the individual key methods below. Holding LEFT or RIGHT
+    /// changes menu values by 10 at a time, holding DISPLAY turns the display off, and
+    /// pressing BACK and ENTER together closes the menu. DISPLAY presses are handled
+    /// when the key is released, so that holding it does not first change the face.
+    pub fn key_event(&mut self, event: KeyEvent) {
+        match event {
+            KeyEvent::Press(Key::Back) => self.key_back(),
+            KeyEvent::Press(Key::Qr) => self.key_qr(),
+            KeyEvent::Press(Key::Display) => self.display_pressed = true,
+            KeyEvent::Release(Key::Display) if self.display_pressed => {
+                self.display_pressed = false;
+                self.key_display();
+            },
+            KeyEvent::LongPress(Key::Display) => {
+                self.display_pressed = false;
+                if !self.snooze_alarm() && !self.dismiss_test_pattern() {
+                    self.face = None;
+                }
+            },
+            KeyEvent::Press(Key::Enter) => self.key_enter(),
+            KeyEvent::Press(Key::Left) => self.key_left(),
+            KeyEvent::Press(Key::Right) => self.key_right(),
+            KeyEvent::LongPress(Key::Left) => self.key_held(false, true),
+            KeyEvent::LongPress(Key::Right) => self.key_held(true, true),
+            KeyEvent::Repeat(Key::Left) => self.key_held(false, false),
+            KeyEvent::Repeat(Key::Right) => self.key_held(true, false),
+            KeyEvent::Chord(Key::Back, Key::Enter) | KeyEvent::Chord(Key::Enter, Key::Back) => {
+                let restored = self.menu.close();
+                if restored {
+                    self.process_menu_update();
+                }
+            },
+            _ => (),
+        }
+    }
+
     /// Call when the BACK key is pressed.
+    ///
+    /// Snoozes a ringing alarm. Leaving a menu setting restores its previous value.
     pub fn key_back(&mut self) {
-        self.menu.back();
+        if self.snooze_alarm() || self.dismiss_test_pattern() {
+            return;
+        }
+        if self.menu.back() {
+            self.process_menu_update();
+        }
     }
 
     /// Call when the QR key is pressed.
+    ///
+    /// Snoozes a ringing alarm.
     pub fn key_qr(&mut self) {
-        self.display_type = match self.display_type {
-            DisplayType::Off => DisplayType::Off,
-            DisplayType::Map => DisplayType::Qr,
-            DisplayType::Qr => DisplayType::Map,
-            DisplayType::Jpeg => DisplayType::Qr,
+        if self.snooze_alarm() || self.dismiss_test_pattern() {
+            return;
+        }
+        self.face = match self.face {
+            None => None,
+            Some(FaceId::Qr) => Some(FaceId::Route),
+            Some(_) => Some(FaceId::Qr),
         }
     }
 
     /// Call when the DISPLAY key is pressed.
+    ///
+    /// Cycles through the faces enabled in the menu, then turns the display off.
+    ///
+    /// Snoozes a ringing alarm.
     pub fn key_display(&mut self) {
-        self.display_type = match self.display_type {
-            DisplayType::Off => DisplayType::Map,
-            DisplayType::Map => DisplayType::Jpeg,
-            DisplayType::Jpeg => DisplayType::Off,
-            DisplayType::Qr => DisplayType::Off,
+        if self.snooze_alarm() || self.dismiss_test_pattern() {
+            return;
         }
+        let start = match self.face {
+            None => 0,
+            Some(face) => FACES.iter().position(|f| *f == face).unwrap() + 1,
+        };
+        self.face = FACES[start..].iter()
+            .find(|f| self.menu.setting_onoff(f.name()) == Some(true))
+            .copied();
     }
 
     /// Call when the ENTER key is pressed.
+    ///
+    /// Dismisses a ringing or snoozed alarm. When the menu is closed, starts or stops
+    /// the timer or stopwatch if shown, and otherwise opens the menu or carries out
+    /// the selected menu action.
     pub fn key_enter(&mut self) {
-        self.menu.enter();
+        if self.alarm != AlarmState::Idle {
+            self.alarm = AlarmState::Idle;
+            self.push_event(Event::AlarmDismissed);
+            return;
+        }
+        if self.dismiss_test_pattern() {
+            return;
+        }
+        match (self.menu.active(), self.face) {
+            (false, Some(FaceId::Timer)) => self.countdown.start_stop(),
+            (false, Some(FaceId::Stopwatch)) => self.stopwatch.start_stop(),
+            _ => if let Some(action) = self.menu.enter() {
+                self.run_action(action);
+            },
+        }
     }
 
     /// Call when the LEFT key is pressed.
+    ///
+    /// Snoozes a ringing alarm. When the menu is closed, shortens the stopped timer
+    /// by a minute or resets the stopped stopwatch, if shown.
     pub fn key_left(&mut self) {
-        if self.menu.active() && self.menu.dec() {
-            self.process_menu_update();
+        if self.snooze_alarm() || self.dismiss_test_pattern() {
+            return;
+        }
+        if self.menu.active() {
+            if self.menu.dec() {
+                self.process_menu_update();
+            }
+        } else if self.face == Some(FaceId::Timer) {
+            self.adjust_timer(-1);
+        } else if self.face == Some(FaceId::Stopwatch) {
+            self.stopwatch.reset();
         }
     }
 
     /// Call when the RIGHT key is pressed.
+    ///
+    /// Snoozes a ringing alarm. When the menu is closed, lengthens the stopped timer
+    /// by a minute or records a lap on the running stopwatch, if shown.
     pub fn key_right(&mut self) {
-        if self.menu.active() && self.menu.inc() {
-            self.process_menu_update();
+        if self.snooze_alarm() || self.dismiss_test_pattern() {
+            return;
+        }
+        if self.menu.active() {
+            if self.menu.inc() {
+                self.process_menu_update();
+            }
+        } else if self.face == Some(FaceId::Timer) {
+            self.adjust_timer(1);
+        } else if self.face == Some(FaceId::Stopwatch) {
+            self.stopwatch.lap();
+        }
+    }
+
+    /// Call while LEFT or RIGHT is held, with `up` true for RIGHT and `first` true
+    /// when the press has just become a long press.
+    ///
+    /// Changes the selected menu value by 10, stopping at the end of its range, or the
+    /// stopped timer by a minute if shown. The first change is by 9, since the press
+    /// itself already changed the value by 1.
+    fn key_held(&mut self, up: bool, first: bool) {
+        if self.alarm == AlarmState::Ringing {
+            return;
+        }
+        if self.menu.active() {
+            let step = if first { 9 } else { 10 };
+            let changed = if up { self.menu.inc_by(step) } else { self.menu.dec_by(step) };
+            if changed {
+                self.process_menu_update();
+            }
+        } else if self.face == Some(FaceId::Timer) {
+            self.adjust_timer(if up { 1 } else { -1 });
         }
     }
 
@@ -330,21 +555,18 @@ impl Clock {
     /// or `key_inc()` with the Clock's setting which may change with calls to `set_date()`
     /// and `set_time()`, so check it between calling those methods.
     pub fn time_changed(&self) -> bool {
-        let (year, month, day, hour, minute, second) = self.menu_time();
+        let (year, month, day, hour, minute, _) = self.menu_time();
+        let DateTime { second, .. } = self.utc;
         self.utc != DateTime { year, month, day, hour, minute, second }
     }
 
     /// Get the current UTC (year, month, day, hour, minute, second) set in the menu.
+    ///
+    /// The menu only sets the time to the minute, so the second is always 0.
     pub fn menu_time(&self) -> (u16, u8, u8, u8, u8, u8) {
-        let dt = self.menu.category(Name::DateTime).unwrap();
-        (
-            (dt.setting_numeric(Name::Year).unwrap()) as u16,
-            dt.setting_numeric(Name::Month).unwrap() as u8,
-            dt.setting_numeric(Name::Day).unwrap() as u8,
-            dt.setting_numeric(Name::Hour).unwrap() as u8,
-            dt.setting_numeric(Name::Minute).unwrap() as u8,
-            dt.setting_numeric(Name::Second).unwrap() as u8,
-        )
+        let (year, month, day) = self.menu.get(key::Date);
+        let (hour, minute) = self.menu.get(key::Time);
+        (year, month, day, hour, minute, 0)
     }
 
     /// Get current desired brightness.
@@ -355,18 +577,32 @@ impl Clock {
     ///
     /// Returns a number 0 to 10.
     pub fn brightness(&self) -> u8 {
-        if self.display_type == DisplayType::Off {
+        // Pulse between full and minimum brightness while an alarm is ringing.
+        if self.alarm == AlarmState::Ringing {
+            return if self.frame & 0x10 == 0 { 10 } else { 1 };
+        }
+
+        if self.test_pattern {
+            return 10;
+        }
+
+        if self.face.is_none() {
             return 0;
         }
 
-        let disp = self.menu.category(Name::Display).unwrap();
-        let brightness = disp.setting_numeric(Name::Brightness).unwrap();
-        if disp.setting_onoff(Name::DimAtNight).unwrap() {
-            let DateTime { hour, .. } = self.local;
-            let start = disp.setting_numeric(Name::DimStartHour).unwrap() as u8;
-            let end = disp.setting_numeric(Name::DimEndHour).unwrap() as u8;
-            if hour >= start || hour < end {
-                disp.setting_numeric(Name::DimBrightness).unwrap() as u8
+        let brightness = self.menu.get(key::Brightness);
+        if self.menu.get(key::DimAtNight) {
+            let DateTime { hour, minute, .. } = self.local;
+            let start = self.menu.get(key::DimStart);
+            let end = self.menu.get(key::DimEnd);
+            let now = (hour, minute);
+            let dim = if start <= end {
+                now >= start && now < end
+            } else {
+                now >= start || now < end
+            };
+            if dim {
+                self.menu.get(key::DimBrightness) as u8
             } else {
                 brightness as u8
             }
@@ -382,7 +618,20 @@ impl Clock {
     ///
     /// Otherwise, let time advance normally, and respect user changes.
     pub fn use_gps_time(&self) -> bool {
-        self.menu.category(Name::DateTime).unwrap().setting_onoff(Name::GPSTime).unwrap()
+        self.menu.get(key::GPSTime)
+    }
+
+    /// Return the next pending event, if any, such as an alarm starting to ring.
+    ///
+    /// Call repeatedly after `set_time()` and key presses until it returns `None`.
+    pub fn poll_event(&mut self) -> Option<Event> {
+        self.events.pop_front()
+    }
+
+    /// Get the number of settings which had invalid values in the most recent
+    /// call to `deserialise()`, and so were reset to their defaults.
+    pub fn invalid_settings(&self) -> usize {
+        self.invalid_settings
     }
 
     /// Return whether the clock settings have changed and need saving since they
@@ -397,18 +646,18 @@ impl Clock {
     /// The data should be rendered to the main display before calling `render_main()`,
     /// which will then draw just the clock elements on top.
     pub fn prerender_jpeg(&self) -> Option<&'static [u8]> {
-        if self.display_type == DisplayType::Jpeg || self.jpeg_override() {
-            self.map.map(|(m, _)| m.image(self.map_day())).flatten()
-        } else {
-            None
+        if self.test_pattern {
+            return None;
         }
+        self.face.and_then(|f| f.jpeg(&self.face_state()))
     }
 
-    /// Serialise state to &[u32], which must be large enough to hold all used settings.
+    /// Serialise state to &[u32], which should be 32 u32s long.
     ///
-    /// This will never exceed 32 u32s.
-    ///
-    /// State includes all menu settings.
+    /// State includes the date the current walk started, the date the clock was
+    /// last running, and every menu setting which differs from its default,
+    /// tagged with a stable ID so that it is kept across firmware upgrades.
+    /// Settings which do not fit in `data` will take their defaults.
     pub fn serialise(&mut self, data: &mut [u32]) {
         self.needs_saving = false;
         // NOTE(unsafe): Menu serialises to u16 and we'd like to pack those into our u32.
@@ -419,12 +668,22 @@ impl Clock {
                 data.len() * 2,
             )
         };
-        data[0] = MENU_VERSION;
-        self.menu.serialise(&mut data[2..]);
+        let start = self.start_date.map(|d| d.to_julian_day()).unwrap_or(0);
+        data[0] = STATE_VERSION;
+        data[2] = start as u16;
+        data[3] = (start >> 16) as u16;
+        let last = self.last_seen.map(|d| d.to_julian_day()).unwrap_or(0);
+        data[4] = last as u16;
+        data[5] = (last >> 16) as u16;
+        store::serialise(&self.menu, &DEFAULT_MENU, &mut data[6..]);
         data[1] = crc16(&data[2..]);
     }
 
     /// Deserialise state from a &[u16] which was previously serialised to.
+    ///
+    /// Settings saved by the original positional format are migrated, with any settings
+    /// they lacked taking their defaults. Settings with invalid values also
+    /// take their defaults, and are counted in `invalid_settings()`.
     pub fn deserialise(&mut self, data: &[u32]) {
         // NOTE(unsafe): Menu serialises from u16 and we'd like to pack those into our u32.
         // NOTE(unsafe): We make sure to not use the incoming slice after making this new one.
@@ -435,12 +694,35 @@ impl Clock {
             )
         };
         let crc = crc16(&data[2..]);
-        if data[0] == MENU_VERSION && data[1] == crc {
-            self.menu.deserialise(&data[2..]);
-            let map_day = self.map_day();
-            self.process_menu_update();
-            self.set_map_day(map_day);
+        if data[1] != crc {
+            return;
         }
+
+        self.menu = DEFAULT_MENU.clone();
+        let (start_date, last_seen) = match data[0] {
+            STATE_VERSION => {
+                let start = (data[2] as u32 | (data[3] as u32) << 16) as i32;
+                let last = (data[4] as u32 | (data[5] as u32) << 16) as i32;
+                self.invalid_settings = store::deserialise(&mut self.menu, &data[6..]);
+                (
+                    Date::from_julian_day(start).ok().filter(|_| start != 0),
+                    Date::from_julian_day(last).ok().filter(|_| last != 0),
+                )
+            },
+            // Version 2 had no start date, so the walk restarts on the first valid time
+            // with the old route day becoming the offset.
+            2 => {
+                let words = data[2..20].try_into().unwrap();
+                self.invalid_settings = store::migrate_v2(&mut self.menu, words);
+                (None, None)
+            },
+            _ => return,
+        };
+        let offset = self.day_offset();
+        self.process_menu_update();
+        self.set_day_offset(offset);
+        self.start_date = start_date.or_else(|| self.route_date());
+        self.last_seen = last_seen;
     }
 }
 
@@ -450,230 +732,252 @@ impl Clock {
         if let Some(data) = map.background() {
             if let Ok(tga) = Tga::from_slice(data) {
                 self.map = Some((map, tga));
-                let map_menu = self.menu.category_mut(Name::Map).unwrap();
-                map_menu.setting_set_choice(Name::Route, map.name());
-                map_menu.setting_set_max(Name::RouteDay, map.days() as i16 - 1);
-                map_menu.setting_set_numeric(Name::RouteDay, 0);
+                self.menu.setting_set_choice(Name::Route, map.name());
+                self.menu.setting_set_max(Name::DayOffset, map.days() as i16 - 1);
+                self.menu.setting_set_numeric(Name::DayOffset, 0);
+                self.start_date = self.route_date();
             }
         }
     }
 
-    /// Get current map day from menu settings.
-    fn map_day(&self) -> u8 {
-        let menu = self.menu.category(Name::Map).unwrap();
-        menu.setting_numeric(Name::RouteDay).unwrap() as u8
+    /// Start a ringing alarm if one is due at the current local time,
+    /// or the snooze period of a snoozed alarm has expired.
+    ///
+    /// Alarms ring once in each local minute, even if it is repeated when the clocks go back.
+    fn check_alarms(&mut self) {
+        let DateTime { hour, minute, .. } = self.local;
+        let now = self.local.minute_start();
+        let due = match self.alarm {
+            AlarmState::Ringing => false,
+            AlarmState::Snoozed { hour: h, minute: m } => (h, m) == (hour, minute),
+            AlarmState::Idle if self.alarm_rung == Some(now) => false,
+            AlarmState::Idle => {
+                match PrimitiveDateTime::try_from(&self.local) {
+                    Ok(local) => self.alarms().any(|a| a.due(local.weekday(), hour, minute)),
+                    Err(_) => false,
+                }
+            },
+        };
+        if due {
+            self.alarm_rung = Some(now);
+            self.alarm = AlarmState::Ringing;
+            self.push_event(Event::AlarmRinging);
+        }
     }
 
-    /// Set the day-of-walk.
-    fn set_map_day(&mut self, day: u8) {
-        if let Some((map, _)) = self.map {
-            if (day as usize) < map.days() {
-                let map_menu = self.menu.category_mut(Name::Map).unwrap();
-                map_menu.setting_set_numeric(Name::RouteDay, day as i16);
-            }
+    /// Get an iterator over all enabled alarms in the menu.
+    fn alarms(&self) -> impl Iterator<Item = Alarm> + '_ {
+        ALARM_SETTINGS.iter().filter(move |(on, ..)| self.menu.setting_onoff(*on).unwrap())
+            .map(move |&(_, time, days)| {
+                let (hour, minute) = self.menu.setting_time(time).unwrap();
+                Alarm { hour, minute, days: alarm::day_mask(self.menu.setting_choice(days).unwrap()) }
+            })
+    }
+
+    /// Snooze the alarm if it is ringing, returning true if so.
+    fn snooze_alarm(&mut self) -> bool {
+        if self.alarm != AlarmState::Ringing {
+            return false;
         }
+        let snooze = self.menu.get(key::SnoozeMinutes) as u16;
+        let DateTime { hour, minute, .. } = self.local;
+        let until = (hour as u16 * 60 + minute as u16 + snooze) % (24 * 60);
+        self.alarm = AlarmState::Snoozed { hour: (until / 60) as u8, minute: (until % 60) as u8 };
+        self.push_event(Event::AlarmSnoozed);
+        true
     }
 
-    /// Get maximum number of days in current map.
-    fn map_days(&self) -> u8 {
-        if let Some((map, _)) = self.map {
-            map.days() as u8
-        } else {
-            0
+    /// Hide the test pattern if it is shown, returning true if so.
+    fn dismiss_test_pattern(&mut self) -> bool {
+        core::mem::replace(&mut self.test_pattern, false)
+    }
+
+    /// Carry out the menu action `action`.
+    fn run_action(&mut self, action: Name) {
+        match action {
+            // Restart the walk from day 0 today.
+            Name::ResetRoute => {
+                self.menu.setting_set_numeric(Name::DayOffset, 0);
+                self.start_date = self.route_date();
+                self.needs_saving = true;
+            },
+            // Restore every setting to its default, keeping the current time in the menu.
+            Name::FactoryReset => {
+                let (year, month, day, hour, minute, _) = self.menu_time();
+                self.menu = DEFAULT_MENU.clone();
+                self.menu.setting_set_date(Name::Date, year, month, day);
+                self.menu.setting_set_time(Name::Time, hour, minute);
+                self.process_menu_update();
+                self.start_date = self.route_date();
+            },
+            Name::TestPattern => self.test_pattern = true,
+            Name::RestartGPS => self.push_event(Event::RestartGPS),
+            _ => (),
         }
     }
 
-    /// Get the current local date/time.
-    ///
-    /// Offsets the internal UTC time by the current UTC offset,
-    /// which may be determined automatically.
-    ///
-    /// Returns (year, month, day, hour, minute, second).
-    fn local_time(&self) -> DateTime {
-        let DateTime { year, month, day, hour, minute, second } = self.utc;
-        let date = Date::from_calendar_date(
-            year as i32, month.try_into().unwrap(), day).unwrap();
-        let time = Time::from_hms(hour, minute, second).unwrap();
-        let utc = PrimitiveDateTime::new(date, time).assume_utc();
-        let local = utc.to_offset(self.utc_offset());
-        DateTime {
-            year: local.year() as u16, month: local.month() as u8, day: local.day(),
-            hour: local.hour(), minute: local.minute(), second: local.second(),
+    /// Change the timer duration by `minutes` if it is not running, saving the
+    /// new duration in the menu.
+    fn adjust_timer(&mut self, minutes: i16) {
+        if self.countdown.running {
+            return;
+        }
+        let current = self.menu.get(key::TimerMinutes);
+        if self.menu.setting_set_numeric(Name::TimerMinutes, current + minutes).is_some() {
+            self.process_menu_update();
         }
     }
 
-    /// Get our current UTC offset at our current UTC time.
-    fn utc_offset(&self) -> UtcOffset
-    {
-        let dt = self.menu.category(Name::DateTime).unwrap();
-        if dt.setting_onoff(Name::AutomaticDST).unwrap() {
-            let DateTime { year, month, day, hour, minute, second } = self.utc;
-            let date = Date::from_calendar_date(
-                year as i32, month.try_into().unwrap(), day).unwrap();
-            let time = Time::from_hms(hour, minute, second).unwrap();
-            let utc = PrimitiveDateTime::new(date, time).assume_utc();
-            Self::automatic_dst(&utc)
-        } else {
-            let off = dt.setting_numeric(Name::UTCOffset).unwrap() as i8;
-            UtcOffset::from_hms(off, 0, 0).unwrap()
+    /// Queue an event for `poll_event()`, discarding the oldest if the queue is full.
+    fn push_event(&mut self, event: Event) {
+        if self.events.is_full() {
+            self.events.pop_front();
         }
+        self.events.push_back(event).ok();
     }
 
-    /// Compute UK UTC offset at given date/time.
-    ///
-    /// Returns UTC+1 between 01:00 UTC on the last Sunday in March
-    /// and 01:00 UTC on the last Sunday in October, and UTC+0 otherwise.
-    fn automatic_dst(time: &OffsetDateTime) -> UtcOffset {
-        // Automatic UK DST.
-        // UTC+1 after 1am UTC on the last Sunday in March,
-        // UTC+0 after 1am UTC on the last Sunday in October.
-        let utc = time.to_offset(UtcOffset::UTC);
-
-        // Find last Sunday in March.
-        let march31 = Date::from_calendar_date(utc.year(), Month::March, 31).unwrap();
-        let days = march31.weekday().number_days_from_sunday();
-        let last_sun = march31 - Duration::days(days as i64);
-        let start = last_sun.with_hms(1, 0, 0).unwrap().assume_utc();
-
-        // Find last Sunday in October.
-        let oct31 = Date::from_calendar_date(utc.year(), Month::October, 31).unwrap();
-        let days = oct31.weekday().number_days_from_sunday();
-        let last_sun = oct31 - Duration::days(days as i64);
-        let end = last_sun.with_hms(1, 0, 0).unwrap().assume_utc();
-
-        // Check if we're inside UK DST.
-        if utc >= start && utc <= end {
-            UtcOffset::from_hms(1, 0, 0).unwrap()
+    /// Get the colour used for text on the main display, from menu settings.
+    fn text_color(&self) -> Rgb888 {
+        let (r, g, b) = self.menu.get(key::TextColor);
+        Rgb888::new(r, g, b)
+    }
+
+    /// Get a snapshot of the current state for rendering faces.
+    fn face_state(&self) -> face::State<'_> {
+        let progress = if self.menu.get(key::LiveWalker) {
+            Progress::Walk {
+                start: self.menu.get(key::WalkStartHour) as u8,
+                end: self.menu.get(key::WalkEndHour) as u8,
+            }
+        } else if self.menu.get(key::AnimateRoute) {
+            Progress::Animate
         } else {
-            UtcOffset::UTC
+            Progress::Complete
+        };
+        face::State {
+            local: self.local,
+            zone: self.zone,
+            frame: self.frame,
+            text_color: self.text_color(),
+            language: self.language(),
+            format: self.format(),
+            map: self.map.as_ref().map(|(m, tga)| (*m, tga)),
+            map_day: self.map_day(),
+            progress,
+            hourly_images: self.menu.get(key::HourlyImages),
+            brightness: self.brightness(),
+            countdown: self.countdown,
+            stopwatch: self.stopwatch,
+            settings: &self.menu,
         }
     }
 
-    /// Render main clock, either with a route or just a plain date/time display.
-    fn render_main_clock<D>(&self, display: &mut D)
-        where D: DrawTarget<Color = Rgb888>
-    {
-        if let Some((map, ref bg)) = self.map {
-            let img = Image::new(bg, Point::zero());
-            img.draw(display).ok();
-            let map_menu = self.menu.category(Name::Map).unwrap();
-            let animate = map_menu.setting_onoff(Name::AnimateRoute).unwrap();
-            map.render(display, &self.local, self.frame, self.map_day(), animate);
-            self.render_main_datetime(display, self.text_color);
-        } else {
-            self.render_big_datetime(display, self.text_color);
+    /// Get the language for text shown on the clock, from menu settings.
+    fn language(&self) -> Language {
+        Language::try_from(self.menu.get(key::Language)).unwrap_or(Language::English)
+    }
+
+    /// Get the format for dates and times shown on the clock, from menu settings.
+    fn format(&self) -> Format {
+        Format {
+            time: TimeFormat::try_from(self.menu.get(key::TimeFormat))
+                .unwrap_or(TimeFormat::Hour24),
+            date: DateFormat::try_from(self.menu.get(key::DateFormat))
+                .unwrap_or(DateFormat::DayMonth),
+            weekday: self.menu.get(key::ShowWeekday),
         }
     }
 
-    /// Render a QR code for the current day, if known.
-    fn render_main_qr<D>(&self, display: &mut D)
-        where D: DrawTarget<Color = Rgb888>
-    {
-        let mut outbuffer = [0u8; 128];
-        let mut tmpbuffer = [0u8; 128];
-        use crate::qr::{QrCode, QrCodeEcc, Version};
-        let url = self.map.map(|(m, _)| m.url(self.map_day())).flatten().unwrap_or(DEFAULT_URL);
-        let qr = QrCode::encode_text(
-            url, &mut tmpbuffer[..], &mut outbuffer[..], QrCodeEcc::Low,
-            Version::new(3), Version::new(3), None, true);
-        if let Ok(qr) = qr {
-            for x in 0..37 {
-                for y in 0..37 {
-                    Pixel(Point::new(13 + x, 13 + y), Rgb888::WHITE).draw(display).ok();
-                }
-            }
-            for y in 0..qr.size() {
-                for x in 0..qr.size() {
-                    if qr.get_module(x, y) {
-                        let x = 13 + 4 + x as i32;
-                        let y = 13 + 4 + y as i32;
-                        Pixel(Point::new(x, y), Rgb888::BLACK).draw(display).ok();
-                    }
+    /// Get current map day.
+    ///
+    /// Counts local days elapsed since the walk started, plus the day offset
+    /// from menu settings, wrapping around at the end of the map.
+    fn map_day(&self) -> u8 {
+        let days = self.map_days() as i32;
+        let offset = self.day_offset() as i32;
+        match (self.start_date, self.route_date()) {
+            (Some(start), Some(today)) if days > 0 => {
+                let day = today.to_julian_day() - start.to_julian_day() + offset;
+                if self.menu.get(key::RouteEnd) == Name::StayAtEnd {
+                    day.clamp(0, days - 1) as u8
+                } else {
+                    day.rem_euclid(days) as u8
                 }
-            }
+            },
+            _ => offset as u8,
         }
     }
 
-    /// Render the date and time on the top-left and top-right of the display.
-    fn render_main_datetime<D>(&self, display: &mut D, color: Rgb888)
-        where D: DrawTarget<Color = Rgb888>
-    {
-        let font = MonoTextStyle::new(&FONT_6X9, color);
-        let tr_style = TextStyleBuilder::new()
-            .alignment(Alignment::Right)
-            .baseline(Baseline::Top)
-            .build();
-        let tl_style = TextStyleBuilder::new()
-            .alignment(Alignment::Left)
-            .baseline(Baseline::Top)
-            .build();
-        let mut s: String<5> = String::new();
-
-        let DateTime { month, day, hour, minute, second, .. } = self.local;
-
-        // Date
-        write!(&mut s, "{:2}/{}", day, month).ok();
-        Text::with_text_style(&s, Point::new(0, 0), font, tl_style).draw(display).ok();
-        s.clear();
+    /// Get the day offset from menu settings.
+    fn day_offset(&self) -> u8 {
+        self.menu.get(key::DayOffset) as u8
+    }
 
-        // Time
-        write!(&mut s, "{:02}", hour).ok();
-        Text::with_text_style(&s, Point::new(49, 0), font, tr_style).draw(display).ok();
-        s.clear();
-        if second % 2 == 0 {
-            Text::with_text_style(":",  Point::new(53, 0), font, tr_style).draw(display).ok();
+    /// Set the day offset, which is added to the number of days since the walk started.
+    fn set_day_offset(&mut self, offset: u8) {
+        if let Some((map, _)) = self.map {
+            if (offset as usize) < map.days() {
+                self.menu.setting_set_numeric(Name::DayOffset, offset as i16);
+            }
         }
-        write!(&mut s, "{:02}", minute).ok();
-        Text::with_text_style(&s, Point::new(63, 0), font, tr_style).draw(display).ok();
-        s.clear();
+    }
 
-        self.render_greetings(display);
+    /// Get maximum number of days in current map.
+    fn map_days(&self) -> u8 {
+        if let Some((map, _)) = self.map {
+            map.days() as u8
+        } else {
+            0
+        }
     }
 
-    /// Render any special greetings for current date.
-    fn render_greetings<D>(&self, display: &mut D) where D: DrawTarget<Color = Rgb888> {
-        let font = MonoTextStyle::new(&FONT_6X9, Rgb888::MAGENTA);
-        let style = TextStyleBuilder::new()
-            .alignment(Alignment::Left)
-            .baseline(Baseline::Top)
-            .build();
+    /// Get the current date for the route, if the time has been set.
+    ///
+    /// This is the local date, except that the day does not roll over until
+    /// the rollover hour from menu settings.
+    fn route_date(&self) -> Option<Date> {
+        if self.time_set {
+            let date = PrimitiveDateTime::try_from(&self.local).ok()?.date();
+            let rollover = self.menu.get(key::RolloverHour);
+            if self.local.hour < rollover as u8 {
+                date.previous_day()
+            } else {
+                Some(date)
+            }
+        } else {
+            None
+        }
+    }
 
-        if self.local.month() == 12 && self.local.day() == 31 {
-            Text::with_text_style("Happy", Point::new(0, 10), font, style).draw(display).ok();
-            Text::with_text_style("NYE!", Point::new(12, 44), font, style).draw(display).ok();
+    /// Get the current local date/time.
+    ///
+    /// Offsets the internal UTC time by the current UTC offset,
+    /// which may be determined automatically.
+    ///
+    /// Returns (year, month, day, hour, minute, second).
+    fn local_time(&self) -> DateTime {
+        let utc = match PrimitiveDateTime::try_from(&self.utc) {
+            Ok(utc) => utc.assume_utc(),
+            Err(_) => return self.local,
+        };
+        let local = utc.to_offset(self.utc_offset());
+        DateTime {
+            year: local.year() as u16, month: local.month() as u8, day: local.day(),
+            hour: local.hour(), minute: local.minute(), second: local.second(),
         }
     }
 
-    /// Render the date and time large and central.
-    fn render_big_datetime<D>(&self, display: &mut D, color: Rgb888)
-        where D: DrawTarget<Color = Rgb888>
+    /// Get our current UTC offset at our current UTC time.
+    fn utc_offset(&self) -> UtcOffset
     {
-        let font = MonoTextStyle::new(&FONT_9X18, color);
-        let style = TextStyleBuilder::new()
-            .alignment(Alignment::Center)
-            .baseline(Baseline::Top)
-            .build();
-        let mut s: String<7> = String::new();
-
-        let DateTime { day, hour, minute, second, .. } = self.local;
-        let month = self.local.month_name_short();
-
-        // Date
-        write!(&mut s, "{} {}", day, month).ok();
-        Text::with_text_style(&s, Point::new(32, 12), font, style).draw(display).ok();
-        s.clear();
-
-        // Time
-        if second % 2 == 0 {
-            write!(&mut s, "{:02}:{:02}", hour, minute).ok();
-        } else {
-            write!(&mut s, "{:02} {:02}", hour, minute).ok();
-        }
-        Text::with_text_style(&s, Point::new(32, 32), font, style).draw(display).ok();
+        PrimitiveDateTime::try_from(&self.utc)
+            .map(|utc| self.zone.offset(&utc))
+            .unwrap_or(UtcOffset::UTC)
     }
 
     /// Render the status screen.
-    /// Shows current date/time, GPS status, and instructions to enter menu.
+    /// Shows current date and time in the chosen format, GPS status, and instructions
+    /// to enter menu.
     fn render_status<D>(&self, display: &mut D)
         where D: DrawTarget<Color = Rgb888>
     {
@@ -682,29 +986,41 @@ impl Clock {
             .alignment(Alignment::Left)
             .baseline(Baseline::Top)
             .build();
-        let mut s: String<17> = String::new();
-
-        let DateTime { year, month, day, hour, minute, second } = self.local;
+        let centre = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+        let mut s: String<40> = String::new();
+        let language = self.language();
+        let format = self.format();
 
-        write!(&mut s, "{:02}/{:02}/{:02} {:02}:{:02}:{:02}",
-               day, month, year - 2000, hour, minute, second).ok();
-        Text::with_text_style(&s, Point::new(0, 0), font, style).draw(display).ok();
+        if format.weekday {
+            write!(&mut s, "{} ", self.local.weekday_name_short(language)).ok();
+        }
+        format.write_date(&mut s, &self.local).ok();
+        Text::with_text_style(&s, Point::new(80, 0), font, centre).draw(display).ok();
         s.clear();
 
-        Text::with_text_style(&self.gps_status, Point::new(0, 20), font, style).draw(display).ok();
-
-        write!(&mut s, "   Press ENTER").ok();
-        Text::with_text_style(&s, Point::new(0, 40), font, style).draw(display).ok();
+        format.write_time(&mut s, &self.local).ok();
+        Text::with_text_style(&s, Point::new(80, 20), font, centre).draw(display).ok();
         s.clear();
 
-        write!(&mut s, "   to open menu").ok();
+        Text::with_text_style(&self.gps_status, Point::new(0, 40), font, style).draw(display).ok();
+
+        // Briefly show how many days were caught up after being switched off.
+        if let Some((days, _)) = self.caught_up {
+            let unit = if days == 1 { Name::OneDay } else { Name::Days };
+            write!(&mut s, "   +{} {}", days, unit.label(language)).ok();
+        } else {
+            write!(&mut s, "   {}", Name::EnterForMenu.label(language)).ok();
+        }
         Text::with_text_style(&s, Point::new(0, 60), font, style).draw(display).ok();
-        s.clear();
     }
 
     /// Render the menu.
-    /// Draws the current category, setting, and value, with left/right arrows
-    /// indicating which is currently being changed by the left/right buttons.
+    /// Draws the path of categories entered, the current item, and its value, with
+    /// left/right arrows indicating which is currently being changed by the left/right buttons,
+    /// and the field being changed underlined for composite values.
     fn render_menu<D>(&self, display: &mut D)
         where D: DrawTarget<Color = Rgb888>
     {
@@ -713,73 +1029,89 @@ impl Clock {
             .alignment(Alignment::Left)
             .baseline(Baseline::Top)
             .build();
-        let mut s: String<17> = String::new();
+        // Strings are sized in bytes, leaving room for accented characters.
+        let mut s: String<40> = String::new();
+        let language = self.language();
 
-        write!(&mut s, "MENU").ok();
+        s.push_str(Name::Menu.label(language)).ok();
         Text::with_text_style(&s, Point::new(58, 0), font, style).draw(display).ok();
         s.clear();
 
-        if !self.menu.category_selected() {
-            write!(&mut s, "< {:^13} >", self.menu.category_name().into_str()).ok();
-        } else {
-            write!(&mut s, "  {:^13}", self.menu.category_name().into_str()).ok();
+        // Show as many of the innermost categories as fit, e.g. "Display > Night".
+        let mut path: String<40> = String::new();
+        for (i, name) in self.menu.path().enumerate() {
+            let sep = if i == 0 { "" } else { " > " };
+            let label = name.label(language);
+            if path.chars().count() + sep.len() + label.chars().count() > 17 {
+                path.clear();
+                path.push_str("..").ok();
+            }
+            path.push_str(sep).ok();
+            path.push_str(label).ok();
         }
+        write!(&mut s, "{:^17}", path).ok();
         Text::with_text_style(&s, Point::new(0, 20), font, style).draw(display).ok();
         s.clear();
 
-        if self.menu.category_selected() && !self.menu.setting_selected() {
-            write!(&mut s, "< {:^14}>", self.menu.setting_name().into_str()).ok();
+        if !self.menu.setting_selected() {
+            write!(&mut s, "< {:^13} >", self.menu.item_name().label(language)).ok();
         } else {
-            write!(&mut s, "  {:^14}", self.menu.setting_name().into_str()).ok();
+            write!(&mut s, "  {:^13}", self.menu.item_name().label(language)).ok();
         }
         Text::with_text_style(&s, Point::new(0, 40), font, style).draw(display).ok();
         s.clear();
 
-        let mut v: String<14> = String::new();
-        self.menu.render_value(&mut v).ok();
-        if self.menu.category_selected() && self.menu.setting_selected() {
+        let mut v: String<32> = String::new();
+        if self.menu.item_is_category() {
+            write!(&mut v, "...").ok();
+        } else {
+            self.menu.render_value(&mut v, language).ok();
+        }
+        if self.menu.setting_selected() && !self.menu.confirming() {
             write!(&mut s, "< {:^14}>", v).ok();
         } else {
             write!(&mut s, "  {:^14}", v).ok();
         }
         Text::with_text_style(&s, Point::new(0, 60), font, style).draw(display).ok();
+
+        if let Some((start, len)) = self.menu.cursor() {
+            let x = (2 + 14usize.saturating_sub(v.chars().count()) / 2 + start) as i32 * 9;
+            Line::new(Point::new(x, 78), Point::new(x + len as i32 * 9 - 1, 78))
+                .into_styled(PrimitiveStyle::with_stroke(Rgb888::WHITE, 1))
+                .draw(display).ok();
+        }
     }
 
     /// Update internal menu state after a value is changed.
     /// Enables/disables fields as appropriate.
     fn process_menu_update(&mut self) {
-        // Enable/disable date/time settings as appropriate.
-        let date = self.menu.category_mut(Name::DateTime).unwrap();
-        let gps = date.setting_onoff(Name::GPSTime).unwrap();
-        date.setting_set_enabled(Name::Year, !gps);
-        date.setting_set_enabled(Name::Month, !gps);
-        date.setting_set_enabled(Name::Day, !gps);
-        date.setting_set_enabled(Name::Hour, !gps);
-        date.setting_set_enabled(Name::Minute, !gps);
-        date.setting_set_enabled(Name::Second, !gps);
-        let dst = date.setting_onoff(Name::AutomaticDST).unwrap();
-        date.setting_set_enabled(Name::UTCOffset, !dst);
-
-        // Limit days in month for current month.
-        let year = date.setting_numeric(Name::Year).unwrap() as i32;
-        let month = (date.setting_numeric(Name::Month).unwrap() as u8).try_into().unwrap();
-        date.setting_set_max(Name::Day, time::util::days_in_year_month(year, month) as i16);
-
-        // Recompute local time immediately when automatic DST is disabled.
-        if !dst {
+        // Enable/disable settings according to their rules.
+        self.menu.update_enabled();
+
+        // Show and adjust dates in the menu in the chosen order.
+        self.menu.set_date_format(self.format().date);
+
+        // Update the parsed time zone, and recompute local time immediately in case
+        // the time zone or offset changed.
+        self.zone = menu_zone(&self.menu);
+        if self.time_set {
             self.local = self.local_time();
         }
 
-        // Enable/disable night-time dimming settings as appropriate.
-        let disp = self.menu.category_mut(Name::Display).unwrap();
-        let dim = disp.setting_onoff(Name::DimAtNight).unwrap();
-        disp.setting_set_enabled(Name::DimBrightness, dim);
-        disp.setting_set_enabled(Name::DimStartHour, dim);
-        disp.setting_set_enabled(Name::DimEndHour, dim);
+        // Update the timer duration if changed.
+        let duration = self.menu.get(key::TimerMinutes) as u32 * 60;
+        if self.countdown.duration != duration {
+            self.countdown.set_duration(duration);
+        }
+
+        // Limit birthday and anniversary days to the month, allowing 29 February.
+        for &(_, month, day, _) in greetings::PERSONAL.iter() {
+            let month = (self.menu.setting_numeric(month).unwrap() as u8).try_into().unwrap();
+            self.menu.setting_set_max(day, time::util::days_in_year_month(2000, month) as i16);
+        }
 
         // Restore map and map-day, also setting map-day maximum value in `set_map()`.
-        let map_menu = self.menu.category(Name::Map).unwrap();
-        let map_choice = map_menu.setting_choice(Name::Route).unwrap();
+        let map_choice = self.menu.get(key::Route);
         if let Ok(map) = Map::try_from(map_choice) {
             if let Some((current_map, _)) = self.map {
                 if map_choice != current_map.name() {
@@ -796,19 +1128,22 @@ impl Clock {
         self.needs_saving = true;
     }
 
-    /// Return whether to override map mode with jpeg mode briefly.
-    ///
-    /// Only returns true if the "Hourly images" setting is enabled,
-    /// the display is currently on map mode, there is an image for
-    /// this day, and it's in the first minute of the hour.
-    fn jpeg_override(&self) -> bool {
-        let map_menu = self.menu.category(Name::Map).unwrap();
-        let hourly_images = map_menu.setting_onoff(Name::HourlyImages).unwrap();
-        let on_map = self.display_type == DisplayType::Map;
-        let got_image = self.map.map(|(m, _)| m.image(self.map_day())).flatten().is_some();
-        let first_minute = self.local.minute == 0;
-
-        hourly_images && on_map && got_image && first_minute
+}
+
+/// Get the time zone from the settings in `menu`, with a fixed offset if set manually.
+fn menu_zone(menu: &ClockMenu) -> TimeZone {
+    match tz::zone_rule(menu.get(key::TimeZone)) {
+        Some(rule) => TimeZone::parse(rule).unwrap_or(TimeZone::UTC),
+        None => TimeZone::fixed(menu.get(key::UTCOffset) as i32 * 60),
+    }
+}
+
+/// Number of seconds from `from` to `to`, which is negative if `to` is earlier,
+/// or zero if either is invalid.
+fn seconds_between(from: &DateTime, to: &DateTime) -> i64 {
+    match (PrimitiveDateTime::try_from(from), PrimitiveDateTime::try_from(to)) {
+        (Ok(from), Ok(to)) => (to - from).whole_seconds(),
+        _ => 0,
     }
 }
 
@@ -838,3 +1173,345 @@ fn crc16(data: &[u16]) -> u16 {
     }
     crc ^ 0xFFFF
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::LONG_PRESS_TICKS;
+
+    fn date(year: i32, month: Month, day: u8) -> Option<Date> {
+        Date::from_calendar_date(year, month, day).ok()
+    }
+
+    /// A clock in London with the route day rolling over at `rollover`.
+    fn london(rollover: i16) -> Clock {
+        let mut clock = Clock::new();
+        clock.menu.setting_set_choice(Name::TimeZone, Name::London);
+        clock.menu.setting_set_numeric(Name::RolloverHour, rollover);
+        clock.process_menu_update();
+        clock
+    }
+
+    /// Set the UTC time on 2024-03-31 and return the local hour and route date.
+    fn spring(clock: &mut Clock, hour: u8, minute: u8) -> (u8, Option<Date>) {
+        clock.set_time(2024, 3, 31, hour, minute, 0).unwrap();
+        (clock.local.hour, clock.route_date())
+    }
+
+    /// Set the UTC time on 2024-10-27 and return the local hour and route date.
+    fn autumn(clock: &mut Clock, hour: u8, minute: u8) -> (u8, Option<Date>) {
+        clock.set_time(2024, 10, 27, hour, minute, 0).unwrap();
+        (clock.local.hour, clock.route_date())
+    }
+
+    #[test]
+    fn rollover_spring_forward() {
+        let (sat, sun) = (date(2024, Month::March, 30), date(2024, Month::March, 31));
+
+        // Clocks go from 01:00 GMT to 02:00 BST, so 02:00 is the first hour after 00:00.
+        let mut clock = london(2);
+        assert_eq!(spring(&mut clock, 0, 59), (0, sat));
+        assert_eq!(spring(&mut clock, 1, 0), (2, sun));
+
+        let mut clock = london(3);
+        assert_eq!(spring(&mut clock, 1, 59), (2, sat));
+        assert_eq!(spring(&mut clock, 2, 0), (3, sun));
+    }
+
+    #[test]
+    fn rollover_hour_skipped() {
+        // Local 01:00 to 01:59 never happens, so the day rolls over at 02:00 BST.
+        let (sat, sun) = (date(2024, Month::March, 30), date(2024, Month::March, 31));
+        let mut clock = london(1);
+        assert_eq!(spring(&mut clock, 0, 0), (0, sat));
+        assert_eq!(spring(&mut clock, 0, 59), (0, sat));
+        assert_eq!(spring(&mut clock, 1, 0), (2, sun));
+    }
+
+    #[test]
+    fn rollover_fall_back() {
+        // Clocks go from 02:00 BST back to 01:00 GMT, so 01:00 happens twice.
+        let (sat, sun) = (date(2024, Month::October, 26), date(2024, Month::October, 27));
+        let mut clock = london(3);
+        assert_eq!(autumn(&mut clock, 1, 59), (1, sat));
+        assert_eq!(autumn(&mut clock, 2, 59), (2, sat));
+        assert_eq!(autumn(&mut clock, 3, 0), (3, sun));
+    }
+
+    #[test]
+    fn rollover_hour_repeated() {
+        // The day rolls over at the first 01:00 and does not go back at the second.
+        let (sat, sun) = (date(2024, Month::October, 26), date(2024, Month::October, 27));
+        let mut clock = london(1);
+        clock.set_time(2024, 10, 26, 23, 30, 0).unwrap();
+        assert_eq!((clock.local.hour, clock.route_date()), (0, sat));
+        assert_eq!(autumn(&mut clock, 0, 0), (1, sun));
+        assert_eq!(autumn(&mut clock, 0, 59), (1, sun));
+        assert_eq!(autumn(&mut clock, 1, 0), (1, sun));
+        assert_eq!(autumn(&mut clock, 1, 59), (1, sun));
+        assert_eq!(autumn(&mut clock, 2, 0), (2, sun));
+
+        // With rollover at 02:00 both 01:00 hours belong to the previous day.
+        let mut clock = london(2);
+        assert_eq!(autumn(&mut clock, 0, 30), (1, sat));
+        assert_eq!(autumn(&mut clock, 1, 30), (1, sat));
+        assert_eq!(autumn(&mut clock, 2, 0), (2, sun));
+    }
+
+    #[test]
+    fn timer_counts_elapsed_seconds() {
+        let mut clock = Clock::new();
+        clock.set_time(2024, 6, 1, 12, 0, 0).unwrap();
+        clock.countdown.set_duration(120);
+        clock.countdown.start_stop();
+        clock.stopwatch.start_stop();
+
+        // A missed update still counts both seconds.
+        clock.set_time(2024, 6, 1, 12, 0, 1).unwrap();
+        clock.set_time(2024, 6, 1, 12, 0, 3).unwrap();
+        assert_eq!(clock.countdown.remaining, 117);
+        assert_eq!(clock.stopwatch.elapsed, 3);
+
+        // Jumps backwards or by more than a minute are not counted.
+        clock.set_time(2024, 6, 1, 11, 59, 0).unwrap();
+        clock.set_time(2024, 6, 1, 13, 0, 0).unwrap();
+        assert_eq!(clock.countdown.remaining, 117);
+        assert_eq!(clock.stopwatch.elapsed, 3);
+
+        clock.set_time(2024, 6, 1, 13, 1, 0).unwrap();
+        assert_eq!(clock.countdown.remaining, 57);
+        assert_eq!(clock.stopwatch.elapsed, 63);
+    }
+
+    #[test]
+    fn timer_finishes_when_update_skips_zero() {
+        let mut clock = Clock::new();
+        clock.set_time(2024, 6, 1, 12, 0, 0).unwrap();
+        clock.countdown.set_duration(2);
+        clock.countdown.start_stop();
+        clock.set_time(2024, 6, 1, 12, 0, 5).unwrap();
+        assert!(clock.countdown.finished());
+        assert!(!clock.countdown.running);
+        assert_eq!(clock.face, Some(FaceId::Timer));
+    }
+
+    #[test]
+    fn route_day_does_not_step_back_at_fall_back() {
+        let mut clock = london(1);
+        clock.set_time(2024, 10, 26, 12, 0, 0).unwrap();
+        let start = clock.start_date.unwrap().to_julian_day();
+        for &(hour, minute) in [(0, 0), (0, 30), (1, 0), (1, 30), (2, 0)].iter() {
+            clock.set_time(2024, 10, 27, hour, minute, 0).unwrap();
+            assert_eq!(clock.route_date().unwrap().to_julian_day() - start, 1);
+        }
+    }
+
+    #[test]
+    fn held_key_keeps_menu_open() {
+        let mut clock = Clock::new();
+        clock.menu.setting_set_numeric(Name::MenuTimeout, 1);
+        clock.menu.enter();
+        let qr = Keys::new().with(Key::Qr, true);
+        for _ in 0..19 {
+            clock.update_keys(Keys::new());
+        }
+        for _ in 0..40 {
+            clock.update_keys(qr);
+        }
+        for _ in 0..19 {
+            clock.update_keys(Keys::new());
+        }
+        assert!(clock.menu.active());
+        clock.update_keys(Keys::new());
+        assert!(!clock.menu.active());
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let mut clock = london(2);
+        clock.menu.setting_set_choice(Name::Route, Name::Shikoku);
+        clock.process_menu_update();
+        clock.set_time(2024, 5, 1, 12, 0, 0).unwrap();
+        clock.set_day_offset(10);
+        clock.menu.setting_set_time(Name::Alarm1Time, 6, 15);
+        clock.menu.setting_set_numeric(Name::Brightness, 4);
+        assert!(clock.needs_saving());
+
+        let mut data = [0u32; 32];
+        clock.serialise(&mut data);
+        assert!(!clock.needs_saving());
+
+        let mut restored = Clock::new();
+        restored.deserialise(&data);
+        assert_eq!(restored.invalid_settings(), 0);
+        assert_eq!(restored.start_date, date(2024, Month::May, 1));
+        assert_eq!(restored.last_seen, date(2024, Month::May, 1));
+        assert_eq!(restored.day_offset(), 10);
+        for &(name, _) in crate::settings::SETTING_IDS.iter() {
+            let saved = clock.menu.setting(name).map(|s| s.serialise());
+            assert_eq!(restored.menu.setting(name).map(|s| s.serialise()), saved, "{:?}", name);
+        }
+
+        // Corrupt state fails its CRC and leaves the defaults.
+        data[10] ^= 1;
+        let mut restored = Clock::new();
+        restored.deserialise(&data);
+        assert_eq!(restored.start_date, None);
+        assert_eq!(restored.menu.setting_choice(Name::Route), Some(Name::NoMap));
+    }
+
+    /// Build state saved by version 2 from the 18 words of its positional menu.
+    fn v2_state(words: [u16; 18]) -> [u32; 32] {
+        let mut data = [0u32; 32];
+        // NOTE(unsafe): As in `serialise()`, view the u32s as u16s.
+        let state = unsafe {
+            core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u16, data.len() * 2)
+        };
+        state[0] = 2;
+        state[2..20].copy_from_slice(&words);
+        state[1] = crc16(&state[2..]);
+        data
+    }
+
+    #[test]
+    fn migrates_version_2_state() {
+        // GPS time off, manual UTC-5, Shikoku on day 42, dimmed to 3 from 22:00 to 06:00.
+        let words = [0, 2024, 5, 1, 0, 0, 0, 0, -5i16 as u16, 1, 42, 0, 1, 6, 1, 3, 22, 6];
+        let mut clock = Clock::new();
+        clock.deserialise(&v2_state(words));
+        assert_eq!(clock.invalid_settings(), 0);
+        assert!(!clock.use_gps_time());
+        assert_eq!(clock.menu.get(key::TimeZone), Name::Manual);
+        assert_eq!(clock.menu.get(key::UTCOffset), -5 * 60);
+        assert_eq!(clock.menu.get(key::Route), Name::Shikoku);
+        assert_eq!(clock.day_offset(), 42);
+        assert_eq!(clock.menu.get(key::Brightness), 6);
+        assert_eq!(clock.menu.get(key::DimBrightness), 3);
+        assert_eq!(clock.menu.get(key::DimStart), (22, 0));
+        assert_eq!(clock.menu.get(key::DimEnd), (6, 0));
+        assert_eq!(clock.start_date, None);
+
+        // The walk starts on the first local date, on the saved route day.
+        clock.set_time(2024, 5, 2, 3, 0, 0).unwrap();
+        assert_eq!(clock.start_date, date(2024, Month::May, 1));
+        assert_eq!(clock.map_day(), 42);
+
+        // Automatic DST was for UK time.
+        let mut clock = Clock::new();
+        clock.deserialise(&v2_state([1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 10, 1, 8, 23, 7]));
+        assert_eq!(clock.invalid_settings(), 0);
+        assert_eq!(clock.menu.get(key::TimeZone), Name::London);
+        assert_eq!(clock.menu.get(key::Route), Name::NoMap);
+    }
+
+    /// A clock in London with alarm 1 set for `hour`:`minute` on `days`.
+    fn alarm(hour: u8, minute: u8, days: Name) -> Clock {
+        let mut clock = london(1);
+        clock.menu.setting_set_onoff(Name::Alarm1, true);
+        clock.menu.setting_set_time(Name::Alarm1Time, hour, minute);
+        clock.menu.setting_set_choice(Name::Alarm1Days, days);
+        clock
+    }
+
+    const RING: Option<Event> = Some(Event::AlarmRinging);
+
+    /// Set the UTC time and return the event it caused, checking there was only one.
+    fn event_at(clock: &mut Clock, time: (u16, u8, u8, u8, u8, u8)) -> Option<Event> {
+        let (year, month, day, hour, minute, second) = time;
+        clock.set_time(year, month, day, hour, minute, second).unwrap();
+        let event = clock.poll_event();
+        assert_eq!(clock.poll_event(), None);
+        event
+    }
+
+    #[test]
+    fn alarm_rings_once_per_minute() {
+        let mut clock = alarm(7, 0, Name::EveryDay);
+        assert_eq!(event_at(&mut clock, (2024, 1, 8, 6, 59, 59)), None);
+        assert_eq!(event_at(&mut clock, (2024, 1, 8, 7, 0, 0)), RING);
+        clock.key_enter();
+        assert_eq!(clock.poll_event(), Some(Event::AlarmDismissed));
+        assert_eq!(event_at(&mut clock, (2024, 1, 8, 7, 0, 30)), None);
+        assert_eq!(event_at(&mut clock, (2024, 1, 8, 7, 1, 0)), None);
+        assert_eq!(event_at(&mut clock, (2024, 1, 9, 7, 0, 0)), RING);
+    }
+
+    #[test]
+    fn alarm_rings_once_in_repeated_hour() {
+        // 01:30 BST and 01:30 GMT are both 01:30 local time when the clocks go back,
+        // which is also the hour the route day rolls over.
+        let mut clock = alarm(1, 30, Name::EveryDay);
+        assert_eq!(event_at(&mut clock, (2024, 10, 27, 0, 29, 0)), None);
+        assert_eq!(event_at(&mut clock, (2024, 10, 27, 0, 30, 0)), RING);
+        clock.key_enter();
+        assert_eq!(clock.poll_event(), Some(Event::AlarmDismissed));
+        assert_eq!(event_at(&mut clock, (2024, 10, 27, 1, 29, 0)), None);
+        assert_eq!(clock.local.hour, 1);
+        assert_eq!(event_at(&mut clock, (2024, 10, 27, 1, 30, 0)), None);
+        assert_eq!(event_at(&mut clock, (2024, 10, 28, 1, 30, 0)), RING);
+    }
+
+    #[test]
+    fn alarm_rings_on_chosen_days() {
+        // 2024-06-01 was a Saturday.
+        let mut clock = alarm(9, 0, Name::Weekdays);
+        assert_eq!(event_at(&mut clock, (2024, 6, 1, 8, 0, 0)), None);
+        assert_eq!(event_at(&mut clock, (2024, 6, 3, 7, 59, 0)), None);
+        assert_eq!(event_at(&mut clock, (2024, 6, 3, 8, 0, 0)), RING);
+    }
+
+    #[test]
+    fn snoozed_alarm_rings_again() {
+        let mut clock = alarm(23, 55, Name::EveryDay);
+        clock.menu.setting_set_numeric(Name::SnoozeMinutes, 9);
+        assert_eq!(event_at(&mut clock, (2024, 1, 8, 23, 55, 0)), RING);
+        clock.key_back();
+        assert_eq!(clock.poll_event(), Some(Event::AlarmSnoozed));
+        assert_eq!(clock.alarm, AlarmState::Snoozed { hour: 0, minute: 4 });
+        assert_eq!(event_at(&mut clock, (2024, 1, 9, 0, 3, 0)), None);
+        assert_eq!(event_at(&mut clock, (2024, 1, 9, 0, 4, 0)), RING);
+        clock.key_enter();
+        assert_eq!(clock.poll_event(), Some(Event::AlarmDismissed));
+        assert_eq!(clock.alarm, AlarmState::Idle);
+    }
+
+    #[test]
+    fn display_key_acts_on_release() {
+        let mut clock = Clock::new();
+        let display = Keys::new().with(Key::Display, true);
+        assert_eq!(clock.face, Some(FaceId::Route));
+
+        // A short press changes the face once the key is released.
+        for _ in 0..5 {
+            clock.update_keys(display);
+        }
+        assert_eq!(clock.face, Some(FaceId::Route));
+        clock.update_keys(Keys::new());
+        assert_eq!(clock.face, Some(FaceId::Photo));
+
+        // A long press turns the display off without changing the face first.
+        for _ in 0..LONG_PRESS_TICKS {
+            clock.update_keys(display);
+            assert_eq!(clock.face, Some(FaceId::Photo));
+        }
+        clock.update_keys(display);
+        assert_eq!(clock.face, None);
+        clock.update_keys(Keys::new());
+        assert_eq!(clock.face, None);
+    }
+
+    #[test]
+    fn time_zone_follows_menu() {
+        let mut clock = london(0);
+        clock.set_time(2024, 7, 1, 12, 0, 0).unwrap();
+        assert_eq!(clock.local.hour, 13);
+        clock.menu.setting_set_choice(Name::TimeZone, Name::Japan);
+        clock.process_menu_update();
+        assert_eq!(clock.local.hour, 21);
+        clock.menu.setting_set_choice(Name::TimeZone, Name::Manual);
+        clock.menu.setting_mut(Name::UTCOffset).unwrap().deserialise(-330i16 as u16);
+        clock.process_menu_update();
+        assert_eq!((clock.local.hour, clock.local.minute), (6, 30));
+        assert_eq!(clock.zone, TimeZone::fixed(-330 * 60));
+    }
+}
@@ -0,0 +1,203 @@
+use core::{convert::Infallible, fmt::Write};
+use heapless::String;
+use embedded_graphics::{
+    image::Image,
+    mono_font::{iso_8859_1::FONT_6X9, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use crate::settings::key;
+use super::{Face, State, min_visible};
+
+/// Fixed-point scale for coordinates and lengths: 16 units per pixel.
+const ONE: i32 = 16;
+
+/// Centre of the dial, in fixed-point units.
+const CENTRE: (i32, i32) = (32 * ONE, 32 * ONE);
+
+/// sin(6° * i) for i in 0..=15, scaled by 16384.
+const SIN: [i32; 16] = [
+    0, 1713, 3406, 5063, 6664, 8192, 9630, 10963,
+    12176, 13255, 14189, 14968, 15582, 16026, 16294, 16384,
+];
+
+const SECOND_HAND_COLOR: Rgb888 = Rgb888::new(255, 40, 40);
+
+/// An anti-aliased analog clock, drawn on black or over the current route.
+///
+/// Shows hour and minute hands, an optional second hand, hour ticks, and the
+/// day of the month in a window at three o'clock.
+pub struct AnalogFace;
+
+impl Face for AnalogFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        let seconds = state.settings.get(key::AnalogSeconds);
+        let over_map = state.settings.get(key::AnalogMap);
+        let color = state.text_color;
+
+        // Anti-aliased edges are blended with whatever is already drawn, so the
+        // face is built up in a local framebuffer and copied to `display` at the end.
+        let mut canvas = Canvas::new(min_alpha(state.brightness, color));
+        if let (true, Some((map, bg))) = (over_map, state.map) {
+            Image::new(bg, Point::zero()).draw(&mut canvas).ok();
+            map.render(&mut canvas, &state.local, state.frame, state.map_day, state.progress);
+        }
+
+        // Hour ticks, longer at the quarter hours.
+        for hour in 0..12 {
+            let inner = if hour % 3 == 0 { 25 * ONE } else { 28 * ONE };
+            let p0 = hand(hour * 60, inner);
+            let p1 = hand(hour * 60, 31 * ONE);
+            canvas.line(p0, p1, ONE, color);
+        }
+
+        // Date window.
+        let font = MonoTextStyle::new(&FONT_6X9, color);
+        let style = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build();
+        let mut s: String<2> = String::new();
+        write!(&mut s, "{}", state.local.day()).ok();
+        Rectangle::new(Point::new(39, 27), Size::new(15, 11)).points()
+            .for_each(|p| canvas.set(p, Rgb888::BLACK));
+        Text::with_text_style(&s, Point::new(46, 32), font, style).draw(&mut canvas).ok();
+
+        // Hands, in 1/720ths of a turn.
+        let hour = state.local.hour() as i32 % 12;
+        let minute = state.local.minute() as i32;
+        let second = state.local.second() as i32;
+        canvas.line(CENTRE, hand(hour * 60 + minute, 16 * ONE), 5 * ONE / 2, color);
+        canvas.line(CENTRE, hand(minute * 12 + second / 5, 26 * ONE), 3 * ONE / 2, color);
+        if seconds {
+            canvas.line(CENTRE, hand(second * 12, 28 * ONE), ONE, SECOND_HAND_COLOR);
+        }
+
+        let area = Rectangle::new(Point::zero(), Size::new(64, 64));
+        display.fill_contiguous(&area, canvas.buf.iter().flatten().copied()).ok();
+    }
+}
+
+/// Get the fixed-point position `length` from the centre at `pos` 1/720ths of a turn
+/// clockwise from twelve o'clock.
+fn hand(pos: i32, length: i32) -> (i32, i32) {
+    let (step, frac) = ((pos / 12) % 60, pos % 12);
+    let (x0, y0) = (sin(step), -sin(step + 15));
+    let (x1, y1) = (sin(step + 1), -sin(step + 16));
+    let x = (x0 * (12 - frac) + x1 * frac) / 12;
+    let y = (y0 * (12 - frac) + y1 * frac) / 12;
+    (CENTRE.0 + x * length / 16384, CENTRE.1 + y * length / 16384)
+}
+
+/// Compute sin(6° * step), scaled by 16384.
+fn sin(step: i32) -> i32 {
+    let step = step.rem_euclid(60) as usize;
+    match step {
+        0..=15 => SIN[step],
+        16..=30 => SIN[30 - step],
+        31..=45 => -SIN[step - 30],
+        _ => -SIN[60 - step],
+    }
+}
+
+/// Get the minimum alpha, out of 255, at which blending `color` over black remains
+/// visible at `brightness`, so that faint anti-aliased edges do not disappear when dimmed.
+fn min_alpha(brightness: u8, color: Rgb888) -> u32 {
+    let max = color.r().max(color.g()).max(color.b()).max(1) as u32;
+    (min_visible(brightness) as u32 * 255 / max).min(255)
+}
+
+/// Integer square root.
+fn isqrt(n: u32) -> u32 {
+    let mut x = n;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// A 64x64 framebuffer which supports anti-aliased drawing.
+struct Canvas {
+    buf: [[Rgb888; 64]; 64],
+    min_alpha: u32,
+}
+
+impl Canvas {
+    fn new(min_alpha: u32) -> Self {
+        Canvas { buf: [[Rgb888::BLACK; 64]; 64], min_alpha }
+    }
+
+    fn set(&mut self, p: Point, color: Rgb888) {
+        if (0..64).contains(&p.x) && (0..64).contains(&p.y) {
+            self.buf[p.y as usize][p.x as usize] = color;
+        }
+    }
+
+    /// Blend `color` into the pixel at (x, y) with `alpha` out of 255.
+    ///
+    /// Alphas too faint to see at the current brightness are either
+    /// raised to the minimum visible alpha or dropped entirely.
+    fn blend(&mut self, x: i32, y: i32, color: Rgb888, alpha: u32) {
+        if alpha * 2 < self.min_alpha {
+            return;
+        }
+        let alpha = alpha.max(self.min_alpha);
+        if let Some(px) = self.buf.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            let mix = |bg: u8, fg: u8| {
+                ((bg as u32 * (255 - alpha) + fg as u32 * alpha) / 255) as u8
+            };
+            *px = Rgb888::new(mix(px.r(), color.r()), mix(px.g(), color.g()), mix(px.b(), color.b()));
+        }
+    }
+
+    /// Draw an anti-aliased line with rounded ends from `p0` to `p1`,
+    /// with fixed-point coordinates and `width`.
+    fn line(&mut self, p0: (i32, i32), p1: (i32, i32), width: i32, color: Rgb888) {
+        let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+        let len2 = dx * dx + dy * dy;
+        let r = width / 2 + ONE / 2;
+        let xmin = (p0.0.min(p1.0) - r) / ONE;
+        let xmax = (p0.0.max(p1.0) + r) / ONE;
+        let ymin = (p0.1.min(p1.1) - r) / ONE;
+        let ymax = (p0.1.max(p1.1) + r) / ONE;
+        for y in ymin..=ymax {
+            for x in xmin..=xmax {
+                // Distance from pixel centre to the nearest point on the line.
+                let (cx, cy) = (x * ONE + ONE / 2 - p0.0, y * ONE + ONE / 2 - p0.1);
+                let t = if len2 == 0 { 0 } else { (cx * dx + cy * dy).clamp(0, len2) };
+                let (qx, qy) = if len2 == 0 { (0, 0) } else { (dx * t / len2, dy * t / len2) };
+                let dist = isqrt(((cx - qx) * (cx - qx) + (cy - qy) * (cy - qy)) as u32) as i32;
+                let coverage = (r - dist).clamp(0, ONE);
+                if coverage > 0 {
+                    self.blend(x, y, color, (coverage * 255 / ONE) as u32);
+                }
+            }
+        }
+    }
+}
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        Size::new(64, 64)
+    }
+}
+
+impl DrawTarget for Canvas {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where I: IntoIterator<Item = Pixel<Self::Color>>
+    {
+        for Pixel(p, color) in pixels {
+            self.set(p, color);
+        }
+        Ok(())
+    }
+}
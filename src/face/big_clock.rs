@@ -0,0 +1,70 @@
+use core::fmt::Write;
+use heapless::String;
+use embedded_graphics::{
+    mono_font::{iso_8859_1::{FONT_6X9, FONT_9X18}, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use crate::format::DateFormat;
+use super::{Face, State};
+
+/// The date and time, large and central.
+pub struct BigClockFace;
+
+impl Face for BigClockFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        let font = MonoTextStyle::new(&FONT_9X18, state.text_color);
+        let small_font = MonoTextStyle::new(&FONT_6X9, state.text_color);
+        let style = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+        let mut s: String<12> = String::new();
+
+        let local = &state.local;
+        let format = &state.format;
+        let am_pm = format.am_pm(local.hour());
+
+        // Move up to make room for a third line with the weekday or AM/PM.
+        let top = if format.weekday || am_pm.is_some() { 4 } else { 12 };
+
+        // Date, with ISO dates in a smaller font to fit
+        let month = local.month_name_short(state.language);
+        match format.date {
+            DateFormat::DayMonth => write!(&mut s, "{} {}", local.day(), month).ok(),
+            DateFormat::MonthDay => write!(&mut s, "{} {}", month, local.day()).ok(),
+            DateFormat::Iso => format.write_date(&mut s, local).ok(),
+        };
+        let (date_font, date_y) = match format.date {
+            DateFormat::Iso => (small_font, top + 5),
+            _ => (font, top),
+        };
+        Text::with_text_style(&s, Point::new(32, date_y), date_font, style).draw(display).ok();
+        s.clear();
+
+        // Time
+        format.write_hour(&mut s, local.hour()).ok();
+        if local.second() % 2 == 0 {
+            write!(&mut s, ":{:02}", local.minute()).ok();
+        } else {
+            write!(&mut s, " {:02}", local.minute()).ok();
+        }
+        Text::with_text_style(&s, Point::new(32, top + 20), font, style).draw(display).ok();
+        s.clear();
+
+        // Weekday and AM/PM
+        if format.weekday {
+            write!(&mut s, "{}", local.weekday_name_short(state.language)).ok();
+        }
+        if let Some(am_pm) = am_pm {
+            let sep = if s.is_empty() { "" } else { " " };
+            write!(&mut s, "{}{}", sep, am_pm).ok();
+        }
+        if !s.is_empty() {
+            Text::with_text_style(&s, Point::new(32, top + 40), font, style).draw(display).ok();
+        }
+    }
+}
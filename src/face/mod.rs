@@ -0,0 +1,319 @@
+use core::fmt::Write;
+use heapless::String;
+use embedded_graphics::{
+    mono_font::{iso_8859_1::{FONT_6X9, FONT_8X13, FONT_10X20}, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use tinytga::Tga;
+use time::PrimitiveDateTime;
+use crate::{Name, clock::{ClockMenu, DateTime}, format::Format, lang::Language};
+use crate::map::{Map, Progress};
+use crate::timer::{Countdown, Stopwatch};
+use crate::greetings;
+use crate::tz::TimeZone;
+
+pub mod analog;
+pub mod big_clock;
+pub mod photo;
+pub mod qr;
+pub mod route;
+pub mod stopwatch;
+pub mod timer;
+pub mod word;
+
+/// Smallest 8-bit colour value which still lights an LED after gamma mapping,
+/// indexed by the number of BCM phases skipped to dim the display
+/// (that is, `10 - brightness`).
+const MIN_VISIBLE: [u8; 10] = [21, 29, 39, 50, 64, 80, 101, 128, 161, 203];
+
+/// Default URL for QR code if no specific entry is known.
+static DEFAULT_URL: &str = "HTTPS://TIMGREIG.CO.UK";
+
+/// Read-only snapshot of the clock state used to render a face.
+pub struct State<'a> {
+    /// Current local date and time.
+    pub local: DateTime,
+
+    /// Local time zone, for greetings on the days the clocks change.
+    pub zone: TimeZone,
+
+    /// Frame counter, incremented every time the main display is rendered.
+    pub frame: u16,
+
+    /// Colour for text drawn over the face.
+    pub text_color: Rgb888,
+
+    /// Language for text drawn over the face.
+    pub language: Language,
+
+    /// Format for dates and times drawn over the face.
+    pub format: Format,
+
+    /// Current map and its decoded background image, if a map is selected.
+    pub map: Option<(Map, &'a Tga<'static, Rgb888>)>,
+
+    /// Current day of the walk.
+    pub map_day: u8,
+
+    /// How much of today's stage to draw.
+    pub progress: Progress,
+
+    /// Whether to show today's image for the first minute of every hour.
+    pub hourly_images: bool,
+
+    /// Current display brightness, from 0 (off) to 10.
+    pub brightness: u8,
+
+    /// Current state of the countdown timer.
+    pub countdown: Countdown,
+
+    /// Current state of the stopwatch.
+    pub stopwatch: Stopwatch,
+
+    /// The clock menu, for looking up face-specific settings and greetings.
+    pub settings: &'a ClockMenu,
+}
+
+impl<'a> State<'a> {
+    /// Get the image for today, if the current map has one.
+    pub fn image(&self) -> Option<&'static [u8]> {
+        self.map.and_then(|(m, _)| m.image(self.map_day))
+    }
+
+    /// Get the URL for today, falling back to a default URL.
+    pub fn url(&self) -> &'static str {
+        self.map.and_then(|(m, _)| m.url(self.map_day)).unwrap_or(DEFAULT_URL)
+    }
+}
+
+/// A watch face for the main display.
+///
+/// Faces render entirely from a `State` snapshot. To add a new face, implement
+/// this trait in a new module and add it to `FaceId` and `FACES`.
+pub trait Face {
+    /// Render this face into `display`.
+    ///
+    /// If `jpeg()` returned an image, it has already been drawn to `display`,
+    /// otherwise the display has been cleared to black.
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>;
+
+    /// Get a JPEG image to draw before calling `render()`, if any.
+    fn jpeg(&self, _state: &State) -> Option<&'static [u8]> {
+        None
+    }
+}
+
+/// Registry of all available faces.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FaceId {
+    Route,
+    Photo,
+    BigClock,
+    Qr,
+    Analog,
+    Word,
+    Timer,
+    Stopwatch,
+}
+
+/// Faces in the order the DISPLAY key cycles through them.
+///
+/// Each face is in the cycle while the on/off setting given by `FaceId::name()` is on.
+pub const FACES: &[FaceId] = &[
+    FaceId::Route, FaceId::Photo, FaceId::BigClock, FaceId::Qr, FaceId::Analog, FaceId::Word,
+    FaceId::Timer, FaceId::Stopwatch,
+];
+
+impl FaceId {
+    /// Name of this face, as shown in the menu.
+    pub const fn name(&self) -> Name {
+        match self {
+            Self::Route     => Name::RouteFace,
+            Self::Photo     => Name::PhotoFace,
+            Self::BigClock  => Name::BigClockFace,
+            Self::Qr        => Name::QrFace,
+            Self::Analog    => Name::AnalogFace,
+            Self::Word      => Name::WordFace,
+            Self::Timer     => Name::TimerFace,
+            Self::Stopwatch => Name::StopwatchFace,
+        }
+    }
+
+    pub fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        match self {
+            Self::Route     => route::RouteFace.render(state, display),
+            Self::Photo     => photo::PhotoFace.render(state, display),
+            Self::BigClock  => big_clock::BigClockFace.render(state, display),
+            Self::Qr        => qr::QrFace.render(state, display),
+            Self::Analog    => analog::AnalogFace.render(state, display),
+            Self::Word      => word::WordFace.render(state, display),
+            Self::Timer     => timer::TimerFace.render(state, display),
+            Self::Stopwatch => stopwatch::StopwatchFace.render(state, display),
+        }
+    }
+
+    pub fn jpeg(&self, state: &State) -> Option<&'static [u8]> {
+        match self {
+            Self::Route     => route::RouteFace.jpeg(state),
+            Self::Photo     => photo::PhotoFace.jpeg(state),
+            Self::BigClock  => big_clock::BigClockFace.jpeg(state),
+            Self::Qr        => qr::QrFace.jpeg(state),
+            Self::Analog    => analog::AnalogFace.jpeg(state),
+            Self::Word      => word::WordFace.jpeg(state),
+            Self::Timer     => timer::TimerFace.jpeg(state),
+            Self::Stopwatch => stopwatch::StopwatchFace.jpeg(state),
+        }
+    }
+}
+
+/// Render a test pattern of colour bars above a grey ramp, for checking the display.
+pub fn render_test_pattern<D>(display: &mut D) where D: DrawTarget<Color = Rgb888> {
+    const BARS: [Rgb888; 8] = [
+        Rgb888::WHITE, Rgb888::YELLOW, Rgb888::CYAN, Rgb888::GREEN,
+        Rgb888::MAGENTA, Rgb888::RED, Rgb888::BLUE, Rgb888::BLACK,
+    ];
+    for (i, color) in BARS.iter().enumerate() {
+        Rectangle::new(Point::new(i as i32 * 8, 0), Size::new(8, 48))
+            .into_styled(PrimitiveStyle::with_fill(*color))
+            .draw(display).ok();
+    }
+    for x in 0..64 {
+        let v = (x * 4 + 3) as u8;
+        Line::new(Point::new(x, 48), Point::new(x, 63))
+            .into_styled(PrimitiveStyle::with_stroke(Rgb888::new(v, v, v), 1))
+            .draw(display).ok();
+    }
+}
+
+/// Get the smallest 8-bit colour value which remains visible at `brightness`.
+pub fn min_visible(brightness: u8) -> u8 {
+    MIN_VISIBLE[10u8.saturating_sub(brightness).min(9) as usize]
+}
+
+/// Get the faintest visible version of `color` at `brightness`.
+pub fn faint(color: Rgb888, brightness: u8) -> Rgb888 {
+    let max = color.r().max(color.g()).max(color.b()).max(1) as u32;
+    let min = min_visible(brightness) as u32;
+    let scale = |c: u8| (c as u32 * min / max) as u8;
+    Rgb888::new(scale(color.r()), scale(color.g()), scale(color.b()))
+}
+
+/// Render the date and time on the top-left and top-right of the display.
+pub fn render_small_datetime<D>(state: &State, display: &mut D)
+    where D: DrawTarget<Color = Rgb888>
+{
+    let font = MonoTextStyle::new(&FONT_6X9, state.text_color);
+    let tr_style = TextStyleBuilder::new()
+        .alignment(Alignment::Right)
+        .baseline(Baseline::Top)
+        .build();
+    let tl_style = TextStyleBuilder::new()
+        .alignment(Alignment::Left)
+        .baseline(Baseline::Top)
+        .build();
+    let mut s: String<5> = String::new();
+
+    let local = &state.local;
+    let format = &state.format;
+
+    // Date
+    format.write_day_month(&mut s, local).ok();
+    Text::with_text_style(&s, Point::new(0, 0), font, tl_style).draw(display).ok();
+    s.clear();
+
+    // Time
+    format.write_hour(&mut s, local.hour()).ok();
+    Text::with_text_style(&s, Point::new(49, 0), font, tr_style).draw(display).ok();
+    s.clear();
+    if local.second() % 2 == 0 {
+        Text::with_text_style(":",  Point::new(53, 0), font, tr_style).draw(display).ok();
+    }
+    write!(&mut s, "{:02}", local.minute()).ok();
+    Text::with_text_style(&s, Point::new(63, 0), font, tr_style).draw(display).ok();
+    s.clear();
+
+    // Weekday and AM/PM beneath the date and time
+    if format.weekday {
+        let weekday = local.weekday_name_short(state.language);
+        Text::with_text_style(weekday, Point::new(0, 9), font, tl_style).draw(display).ok();
+    }
+    if let Some(am_pm) = format.am_pm(local.hour()) {
+        Text::with_text_style(am_pm, Point::new(63, 9), font, tr_style).draw(display).ok();
+    }
+
+    render_greetings(state, display);
+}
+
+/// Render a duration in seconds in large digits in the centre of the display.
+///
+/// Durations of an hour or more are shown as H:MM:SS in a smaller font to fit.
+pub fn render_duration<D>(state: &State, seconds: u32, display: &mut D)
+    where D: DrawTarget<Color = Rgb888>
+{
+    let style = TextStyleBuilder::new()
+        .alignment(Alignment::Center)
+        .baseline(Baseline::Middle)
+        .build();
+    let mut s: String<7> = String::new();
+    let (h, m, sec) = ((seconds / 3600) % 10, (seconds / 60) % 60, seconds % 60);
+    let font = if h == 0 {
+        write!(&mut s, "{:02}:{:02}", m, sec).ok();
+        MonoTextStyle::new(&FONT_10X20, state.text_color)
+    } else {
+        write!(&mut s, "{}:{:02}:{:02}", h, m, sec).ok();
+        MonoTextStyle::new(&FONT_8X13, state.text_color)
+    };
+    Text::with_text_style(&s, Point::new(32, 32), font, style).draw(display).ok();
+}
+
+/// Render a two-pixel ring around the border of the display, lit clockwise
+/// from twelve o'clock for `num`/`den` of its length and faint elsewhere.
+pub fn render_ring<D>(state: &State, num: u32, den: u32, display: &mut D)
+    where D: DrawTarget<Color = Rgb888>
+{
+    const PERIMETER: u32 = 4 * 63;
+    let lit = (num.min(den) * PERIMETER).checked_div(den).unwrap_or(0);
+    let dim = faint(state.text_color, state.brightness);
+    for i in 0..PERIMETER {
+        let color = if i < lit { state.text_color } else { dim };
+        let p = match (i + 32) % PERIMETER {
+            i @ 0..=62 => Point::new(i as i32, 0),
+            i @ 63..=125 => Point::new(63, i as i32 - 63),
+            i @ 126..=188 => Point::new(189 - i as i32, 63),
+            i => Point::new(0, 252 - i as i32),
+        };
+        // Second pixel is one step in towards the centre.
+        let q = Point::new(p.x.clamp(1, 62), p.y.clamp(1, 62));
+        Pixel(p, color).draw(display).ok();
+        Pixel(q, color).draw(display).ok();
+    }
+}
+
+/// Render any special greetings for current date, rotating between them if there are several.
+fn render_greetings<D>(state: &State, display: &mut D) where D: DrawTarget<Color = Rgb888> {
+    let date = match PrimitiveDateTime::try_from(&state.local) {
+        Ok(local) => local.date(),
+        Err(_) => return,
+    };
+    let todays = greetings::greetings(date, &state.zone, state.settings);
+    if todays.is_empty() {
+        return;
+    }
+    let seconds = state.local.minute() as u16 * 60 + state.local.second() as u16;
+    let greeting = todays[(seconds / greetings::ROTATE_SECONDS) as usize % todays.len()];
+
+    let font = MonoTextStyle::new(greeting.font, greeting.color);
+    let style = TextStyleBuilder::new()
+        .alignment(Alignment::Left)
+        .baseline(Baseline::Top)
+        .build();
+    for &(point, text) in greeting.lines[state.language as usize].iter() {
+        Text::with_text_style(text, point, font, style).draw(display).ok();
+    }
+}
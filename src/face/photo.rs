@@ -0,0 +1,17 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+use super::{Face, State, render_small_datetime};
+
+/// Today's image from the current route, with the date and time along the top.
+pub struct PhotoFace;
+
+impl Face for PhotoFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        render_small_datetime(state, display);
+    }
+
+    fn jpeg(&self, state: &State) -> Option<&'static [u8]> {
+        state.image()
+    }
+}
@@ -0,0 +1,34 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, Pixel};
+use crate::qr::{QrCode, QrCodeEcc, Version};
+use super::{Face, State};
+
+/// A QR code linking to information about today's stage of the route.
+pub struct QrFace;
+
+impl Face for QrFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        let mut outbuffer = [0u8; 128];
+        let mut tmpbuffer = [0u8; 128];
+        let qr = QrCode::encode_text(
+            state.url(), &mut tmpbuffer[..], &mut outbuffer[..], QrCodeEcc::Low,
+            Version::new(3), Version::new(3), None, true);
+        if let Ok(qr) = qr {
+            for x in 0..37 {
+                for y in 0..37 {
+                    Pixel(Point::new(13 + x, 13 + y), Rgb888::WHITE).draw(display).ok();
+                }
+            }
+            for y in 0..qr.size() {
+                for x in 0..qr.size() {
+                    if qr.get_module(x, y) {
+                        let x = 13 + 4 + x as i32;
+                        let y = 13 + 4 + y as i32;
+                        Pixel(Point::new(x, y), Rgb888::BLACK).draw(display).ok();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use embedded_graphics::{image::Image, pixelcolor::Rgb888, prelude::*};
+use super::{Face, State, big_clock::BigClockFace, render_small_datetime};
+
+/// Map of the current route with the date and time along the top.
+///
+/// Falls back to the big clock when no map is selected. If hourly images are
+/// enabled, today's image is shown instead for the first minute of every hour.
+pub struct RouteFace;
+
+impl RouteFace {
+    fn show_image(&self, state: &State) -> bool {
+        state.hourly_images && state.local.minute() == 0 && state.image().is_some()
+    }
+}
+
+impl Face for RouteFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        if self.show_image(state) {
+            render_small_datetime(state, display);
+        } else if let Some((map, bg)) = state.map {
+            Image::new(bg, Point::zero()).draw(display).ok();
+            map.render(display, &state.local, state.frame, state.map_day, state.progress);
+            render_small_datetime(state, display);
+        } else {
+            BigClockFace.render(state, display);
+        }
+    }
+
+    fn jpeg(&self, state: &State) -> Option<&'static [u8]> {
+        if self.show_image(state) {
+            state.image()
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt::Write;
+use heapless::String;
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_6X9, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use crate::Name;
+use super::{Face, State, render_duration, render_ring};
+
+/// A stopwatch, with a ring around the border sweeping once a minute.
+///
+/// Shows the most recent lap time underneath.
+pub struct StopwatchFace;
+
+impl Face for StopwatchFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        let watch = &state.stopwatch;
+        let font = MonoTextStyle::new(&FONT_6X9, state.text_color);
+        let style = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+
+        render_ring(state, watch.elapsed % 60, 60, display);
+        render_duration(state, watch.elapsed, display);
+        let title = Name::StopwatchTitle.label(state.language);
+        Text::with_text_style(title, Point::new(32, 8), font, style).draw(display).ok();
+
+        if let Some(lap) = watch.lap {
+            let mut s: String<10> = String::new();
+            let name = Name::Lap.label(state.language);
+            write!(&mut s, "{} {:02}:{:02}", name, (lap / 60) % 100, lap % 60).ok();
+            Text::with_text_style(&s, Point::new(32, 46), font, style).draw(display).ok();
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_6X9, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use crate::Name;
+use super::{Face, State, render_duration, render_ring};
+
+/// A countdown timer, with a ring around the border showing the time remaining.
+///
+/// Flashes when the timer has finished, until it is reset.
+pub struct TimerFace;
+
+impl Face for TimerFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        let timer = &state.countdown;
+        let label = |name: Name| name.label(state.language);
+        let font = MonoTextStyle::new(&FONT_6X9, state.text_color);
+        let style = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+
+        if timer.finished() {
+            if state.frame & 0x10 == 0 {
+                render_ring(state, 1, 1, display);
+                render_duration(state, 0, display);
+                let done = label(Name::TimerDone);
+                Text::with_text_style(done, Point::new(32, 46), font, style).draw(display).ok();
+            }
+            return;
+        }
+
+        render_ring(state, timer.remaining, timer.duration, display);
+        render_duration(state, timer.remaining, display);
+        let status = if timer.running {
+            ""
+        } else if timer.remaining == timer.duration {
+            label(Name::TimerReady)
+        } else {
+            label(Name::TimerPaused)
+        };
+        let title = label(Name::TimerTitle);
+        Text::with_text_style(title, Point::new(32, 8), font, style).draw(display).ok();
+        Text::with_text_style(status, Point::new(32, 46), font, style).draw(display).ok();
+    }
+}
@@ -0,0 +1,119 @@
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_4X6, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    text::{Baseline, Text},
+};
+use crate::settings::key;
+use super::{Face, State, faint};
+
+/// Letters of the word clock, 16 columns by 10 rows of 4x6 pixel characters.
+///
+/// Minute words come first, followed by MINUTES, PAST/TO, the hours, and OCLOCK,
+/// with overlapping words (such as TWONE and FIVEIGHTEN) to fit exact minutes.
+const GRID: [&[u8; 16]; 10] = [
+    b"TWENTYXQUARTERZK",
+    b"HALFZFIVEIGHTEEN",
+    b"TWONEFOURTEENTEN",
+    b"THREETWELVELEVEN",
+    b"SIXTEENSEVENTEEN",
+    b"THIRTEENINETEENX",
+    b"MINUTESPASTOFOUR",
+    b"TWONESEVENINESIX",
+    b"TWELVELEVENTHREE",
+    b"FIVEIGHTENOCLOCK",
+];
+
+/// Position of a word in `GRID`, as (row, column, length).
+type Word = (u8, u8, u8);
+
+/// Words for one to nineteen minutes, with QUARTER for fifteen.
+const NUMBERS: [Word; 19] = [
+    (2, 2, 3), (2, 0, 3), (3, 0, 5), (2, 5, 4), (1, 5, 4), (4, 0, 3), (4, 7, 5),
+    (1, 8, 5), (5, 7, 4), (2, 13, 3), (3, 10, 6), (3, 5, 6), (5, 0, 8), (2, 5, 8),
+    (0, 7, 7), (4, 0, 7), (4, 7, 9), (1, 8, 8), (5, 7, 8),
+];
+
+/// Words for the hours, from TWELVE to ELEVEN.
+const HOURS: [Word; 12] = [
+    (8, 0, 6), (7, 2, 3), (7, 0, 3), (8, 11, 5), (6, 12, 4), (9, 0, 4),
+    (7, 13, 3), (7, 5, 5), (9, 3, 5), (7, 9, 4), (9, 7, 3), (8, 5, 6),
+];
+
+const TWENTY: Word = (0, 0, 6);
+const HALF: Word = (1, 0, 4);
+const MINUTE: Word = (6, 0, 6);
+const MINUTES: Word = (6, 0, 7);
+const PAST: Word = (6, 7, 4);
+const TO: Word = (6, 10, 2);
+const OCLOCK: Word = (9, 10, 6);
+
+/// The time in words, such as "TEN PAST SIX", lit up on a grid of faint letters.
+///
+/// By default the time is rounded down to five minutes; the "Exact minutes"
+/// setting instead shows phrases such as "TWENTY THREE MINUTES PAST SIX".
+pub struct WordFace;
+
+impl Face for WordFace {
+    fn render<D>(&self, state: &State, display: &mut D)
+        where D: DrawTarget<Color = Rgb888>
+    {
+        let exact = state.settings.get(key::WordExact);
+        let lit = lit_words(state.local.hour(), state.local.minute(), exact);
+        let on = MonoTextStyle::new(&FONT_4X6, state.text_color);
+        let off = MonoTextStyle::new(&FONT_4X6, faint(state.text_color, state.brightness));
+
+        let mut buf = [0u8; 4];
+        for (row, letters) in GRID.iter().enumerate() {
+            for (col, letter) in letters.iter().enumerate() {
+                let style = if lit[row] & (1 << col) != 0 { on } else { off };
+                let s = (*letter as char).encode_utf8(&mut buf);
+                let p = Point::new(col as i32 * 4, 2 + row as i32 * 6);
+                Text::with_baseline(s, p, style, Baseline::Top).draw(display).ok();
+            }
+        }
+    }
+}
+
+/// Get a bitmask of lit letters for each row of `GRID`, with column 0 as bit 0.
+fn lit_words(hour: u8, minute: u8, exact: bool) -> [u16; 10] {
+    let mut lit = [0u16; 10];
+    let mut light = |(row, col, len): Word| {
+        lit[row as usize] |= (((1u32 << len) - 1) << col) as u16;
+    };
+
+    let minute = if exact { minute } else { minute - minute % 5 };
+    let (count, hour) = match minute {
+        0 => {
+            light(OCLOCK);
+            (0, hour)
+        },
+        1..=30 => {
+            light(PAST);
+            (minute, hour)
+        },
+        _ => {
+            light(TO);
+            (60 - minute, hour + 1)
+        },
+    };
+    light(HOURS[hour as usize % 12]);
+
+    match count {
+        0 => (),
+        30 => light(HALF),
+        _ => {
+            if count >= 20 {
+                light(TWENTY);
+            }
+            if count % 20 != 0 {
+                light(NUMBERS[(count % 20) as usize - 1]);
+            }
+            if exact && count != 15 {
+                light(if count == 1 { MINUTE } else { MINUTES });
+            }
+        },
+    }
+
+    lit
+}
@@ -0,0 +1,108 @@
+use core::fmt::{self, Write};
+use crate::{Name, clock::DateTime};
+
+/// Choices for the time format setting.
+pub const TIME_FORMAT_NAMES: &[Name] = &[Name::Hour24, Name::Hour12];
+
+/// Choices for the date format setting.
+pub const DATE_FORMAT_NAMES: &[Name] = &[Name::DayMonth, Name::MonthDay, Name::IsoDate];
+
+/// How the hour of the day is shown.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimeFormat {
+    /// Hours 00 to 23.
+    Hour24,
+    /// Hours 1 to 12 with AM or PM.
+    Hour12,
+}
+
+/// Order of the fields in a date.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DateFormat {
+    /// Day before month, as DD/MM/YY.
+    DayMonth,
+    /// Month before day, as MM/DD/YY.
+    MonthDay,
+    /// ISO 8601, as YYYY-MM-DD.
+    Iso,
+}
+
+impl TryFrom<Name> for TimeFormat {
+    type Error = ();
+    fn try_from(name: Name) -> Result<TimeFormat, ()> {
+        match name {
+            Name::Hour24    => Ok(TimeFormat::Hour24),
+            Name::Hour12    => Ok(TimeFormat::Hour12),
+            _               => Err(()),
+        }
+    }
+}
+
+impl TryFrom<Name> for DateFormat {
+    type Error = ();
+    fn try_from(name: Name) -> Result<DateFormat, ()> {
+        match name {
+            Name::DayMonth  => Ok(DateFormat::DayMonth),
+            Name::MonthDay  => Ok(DateFormat::MonthDay),
+            Name::IsoDate   => Ok(DateFormat::Iso),
+            _               => Err(()),
+        }
+    }
+}
+
+/// How dates and times are shown on the main display and status screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Format {
+    pub time: TimeFormat,
+    pub date: DateFormat,
+    /// Whether to show the day of the week alongside the date.
+    pub weekday: bool,
+}
+
+impl Format {
+    /// Write `hour` as two characters, zero-padded for 24-hour time
+    /// or space-padded from 1 to 12 for 12-hour time.
+    pub fn write_hour<W: Write>(&self, mut w: W, hour: u8) -> fmt::Result {
+        match self.time {
+            TimeFormat::Hour24 => write!(w, "{:02}", hour),
+            TimeFormat::Hour12 => write!(w, "{:2}", (hour + 11) % 12 + 1),
+        }
+    }
+
+    /// Get "AM" or "PM" for `hour` in 12-hour time, or None for 24-hour time.
+    pub fn am_pm(&self, hour: u8) -> Option<&'static str> {
+        match self.time {
+            TimeFormat::Hour24 => None,
+            TimeFormat::Hour12 => Some(if hour < 12 { "AM" } else { "PM" }),
+        }
+    }
+
+    /// Write the time including seconds, followed by AM or PM for 12-hour time.
+    pub fn write_time<W: Write>(&self, mut w: W, dt: &DateTime) -> fmt::Result {
+        self.write_hour(&mut w, dt.hour())?;
+        write!(w, ":{:02}:{:02}", dt.minute(), dt.second())?;
+        match self.am_pm(dt.hour()) {
+            Some(am_pm) => write!(w, " {}", am_pm),
+            None => Ok(()),
+        }
+    }
+
+    /// Write the date in full, with a two-digit year unless ISO 8601.
+    pub fn write_date<W: Write>(&self, mut w: W, dt: &DateTime) -> fmt::Result {
+        let (day, month, year) = (dt.day(), dt.month(), dt.year());
+        match self.date {
+            DateFormat::DayMonth => write!(w, "{:02}/{:02}/{:02}", day, month, year % 100),
+            DateFormat::MonthDay => write!(w, "{:02}/{:02}/{:02}", month, day, year % 100),
+            DateFormat::Iso => write!(w, "{:04}-{:02}-{:02}", year, month, day),
+        }
+    }
+
+    /// Write the day and month in at most five characters.
+    pub fn write_day_month<W: Write>(&self, mut w: W, dt: &DateTime) -> fmt::Result {
+        match self.date {
+            DateFormat::DayMonth => write!(w, "{:2}/{}", dt.day(), dt.month()),
+            DateFormat::MonthDay => write!(w, "{:2}/{}", dt.month(), dt.day()),
+            DateFormat::Iso => write!(w, "{:02}-{:02}", dt.month(), dt.day()),
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use heapless::Vec;
+use time::{Date, Month, Weekday};
+use embedded_graphics::{
+    mono_font::{MonoFont, iso_8859_1::{FONT_5X8, FONT_6X9}},
+    pixelcolor::Rgb888,
+    prelude::*,
+};
+use crate::{Name, lang::N_LANGUAGES, menu::Menu, tz::TimeZone};
+
+/// Number of seconds each greeting is shown for when several fall on the same day.
+pub const ROTATE_SECONDS: u16 = 5;
+
+/// Maximum number of greetings shown on any one day.
+pub const MAX_GREETINGS: usize = 8;
+
+/// Rule for which day of the year an event falls on.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum When {
+    /// The same date every year.
+    ///
+    /// Events on 29 February are observed on 28 February in other years.
+    Fixed { month: Month, day: u8 },
+
+    /// The `n`th `weekday` of `month`, counting from the end of the month if `n` is negative,
+    /// so `n = -1` is the last one.
+    ///
+    /// No built-in holiday needs this yet.
+    #[allow(unused)]
+    NthWeekday { month: Month, weekday: Weekday, n: i8 },
+
+    /// A number of days before or after Easter Sunday.
+    Easter { offset: i16 },
+
+    /// The day the clocks go forward, or back if `forward` is false, in the local time zone.
+    ClocksChange { forward: bool },
+}
+
+impl When {
+    /// Check whether this rule falls on `date` in the time zone `zone`.
+    pub fn matches(&self, date: Date, zone: &TimeZone) -> bool {
+        match *self {
+            When::Fixed { month, day } => {
+                let leap = time::util::is_leap_year(date.year());
+                if month == Month::February && day == 29 && !leap {
+                    date.month() == Month::February && date.day() == 28
+                } else {
+                    date.month() == month && date.day() == day
+                }
+            },
+            When::NthWeekday { month, weekday, n } => {
+                if date.month() != month || date.weekday() != weekday {
+                    return false;
+                }
+                let days = time::util::days_in_year_month(date.year(), month);
+                if n > 0 {
+                    (date.day() - 1) / 7 + 1 == n as u8
+                } else {
+                    (days - date.day()) / 7 + 1 == n.unsigned_abs()
+                }
+            },
+            When::Easter { offset } => match easter(date.year()) {
+                Some(easter) => easter.to_julian_day() + offset as i32 == date.to_julian_day(),
+                None => false,
+            },
+            When::ClocksChange { forward } => zone.change_on(date) == Some(forward),
+        }
+    }
+}
+
+/// A greeting drawn over the clock face.
+pub struct Greeting {
+    /// Each line of text and the position of its top-left corner, in each language.
+    pub lines: [&'static [(Point, &'static str)]; N_LANGUAGES],
+    pub color: Rgb888,
+    pub font: &'static MonoFont<'static>,
+}
+
+/// Calendar of holidays, shown when enabled in the Greetings menu.
+pub const CALENDAR: &[(When, Greeting)] = &[
+    (When::Fixed { month: Month::January, day: 1 }, Greeting {
+        lines: [
+            &[(Point::new(0, 10), "Happy"), (Point::new(0, 44), "New Year!")],
+            &[(Point::new(0, 10), "Bonne"), (Point::new(0, 44), "année!")],
+            &[(Point::new(0, 10), "Feliz"), (Point::new(0, 44), "Año Nuevo!")],
+            &[(Point::new(0, 10), "Buon"), (Point::new(0, 44), "anno!")],
+            &[(Point::new(0, 10), "Prosit"), (Point::new(0, 44), "Neujahr!")],
+        ],
+        color: Rgb888::MAGENTA, font: &FONT_6X9,
+    }),
+    (When::ClocksChange { forward: true }, Greeting {
+        lines: [
+            &[(Point::new(0, 10), "Clocks"), (Point::new(0, 44), "forward!")],
+            &[(Point::new(0, 10), "Heure"), (Point::new(0, 44), "d'été!")],
+            &[(Point::new(0, 10), "Horario"), (Point::new(0, 44), "de verano!")],
+            &[(Point::new(0, 10), "Ora"), (Point::new(0, 44), "legale!")],
+            &[(Point::new(0, 10), "Sommer-"), (Point::new(0, 44), "zeit!")],
+        ],
+        color: Rgb888::CYAN, font: &FONT_6X9,
+    }),
+    (When::Easter { offset: -2 }, Greeting {
+        lines: [
+            &[(Point::new(0, 44), "Good Friday")],
+            &[(Point::new(0, 10), "Vendredi"), (Point::new(0, 44), "saint")],
+            &[(Point::new(0, 10), "Viernes"), (Point::new(0, 44), "Santo")],
+            &[(Point::new(0, 10), "Venerdì"), (Point::new(0, 44), "Santo")],
+            &[(Point::new(0, 44), "Karfreitag")],
+        ],
+        color: Rgb888::YELLOW, font: &FONT_5X8,
+    }),
+    (When::Easter { offset: 0 }, Greeting {
+        lines: [
+            &[(Point::new(0, 10), "Happy"), (Point::new(0, 44), "Easter!")],
+            &[(Point::new(0, 10), "Joyeuses"), (Point::new(0, 44), "Pâques!")],
+            &[(Point::new(0, 10), "Felices"), (Point::new(0, 44), "Pascuas!")],
+            &[(Point::new(0, 10), "Buona"), (Point::new(0, 44), "Pasqua!")],
+            &[(Point::new(0, 10), "Frohe"), (Point::new(0, 44), "Ostern!")],
+        ],
+        color: Rgb888::YELLOW, font: &FONT_6X9,
+    }),
+    (When::ClocksChange { forward: false }, Greeting {
+        lines: [
+            &[(Point::new(0, 10), "Clocks"), (Point::new(0, 44), "back!")],
+            &[(Point::new(0, 10), "Heure"), (Point::new(0, 44), "d'hiver!")],
+            &[(Point::new(0, 10), "Cambio"), (Point::new(0, 44), "de hora!")],
+            &[(Point::new(0, 10), "Ora"), (Point::new(0, 44), "solare!")],
+            &[(Point::new(0, 10), "Winter-"), (Point::new(0, 44), "zeit!")],
+        ],
+        color: Rgb888::CYAN, font: &FONT_6X9,
+    }),
+    (When::Fixed { month: Month::December, day: 25 }, Greeting {
+        lines: [
+            &[(Point::new(0, 10), "Merry"), (Point::new(0, 44), "Christmas!")],
+            &[(Point::new(0, 10), "Joyeux"), (Point::new(0, 44), "Noël!")],
+            &[(Point::new(0, 10), "Feliz"), (Point::new(0, 44), "Navidad!")],
+            &[(Point::new(0, 10), "Buon"), (Point::new(0, 44), "Natale!")],
+            &[(Point::new(0, 10), "Frohes"), (Point::new(0, 44), "Fest!")],
+        ],
+        color: Rgb888::RED, font: &FONT_6X9,
+    }),
+    (When::Fixed { month: Month::December, day: 31 }, Greeting {
+        lines: [
+            &[(Point::new(0, 10), "Happy"), (Point::new(12, 44), "NYE!")],
+            &[(Point::new(0, 10), "Bon"), (Point::new(0, 44), "réveillon!")],
+            &[(Point::new(0, 10), "Feliz"), (Point::new(0, 44), "Nochevieja")],
+            &[(Point::new(0, 10), "Buon San"), (Point::new(0, 44), "Silvestro!")],
+            &[(Point::new(0, 10), "Guten"), (Point::new(0, 44), "Rutsch!")],
+        ],
+        color: Rgb888::MAGENTA, font: &FONT_6X9,
+    }),
+];
+
+const BIRTHDAY: Greeting = Greeting {
+    lines: [
+        &[(Point::new(0, 10), "Happy"), (Point::new(0, 44), "Birthday!")],
+        &[
+            (Point::new(0, 10), "Joyeux"), (Point::new(0, 44), "anniver-"),
+            (Point::new(0, 53), "saire!"),
+        ],
+        &[(Point::new(0, 10), "Feliz"), (Point::new(0, 44), "cumpleaños")],
+        &[(Point::new(0, 10), "Buon"), (Point::new(0, 44), "compleanno")],
+        &[
+            (Point::new(0, 10), "Alles Gute"), (Point::new(0, 44), "zum"),
+            (Point::new(0, 53), "Geburtstag"),
+        ],
+    ],
+    color: Rgb888::GREEN, font: &FONT_6X9,
+};
+
+const ANNIVERSARY: Greeting = Greeting {
+    lines: [
+        &[(Point::new(0, 10), "Happy"), (Point::new(0, 44), "Anniversary!")],
+        &[(Point::new(0, 10), "Joyeux"), (Point::new(0, 44), "anniversaire")],
+        &[(Point::new(0, 10), "Feliz"), (Point::new(0, 44), "aniversario!")],
+        &[(Point::new(0, 10), "Buon"), (Point::new(0, 44), "anniversario")],
+        &[(Point::new(0, 10), "Frohen"), (Point::new(0, 44), "Jahrestag!")],
+    ],
+    color: Rgb888::GREEN, font: &FONT_5X8,
+};
+
+/// Personal events from the Greetings menu: on/off, month, and day settings, and greeting.
+pub const PERSONAL: [(Name, Name, Name, &Greeting); 3] = [
+    (Name::Birthday1, Name::Birthday1Month, Name::Birthday1Day, &BIRTHDAY),
+    (Name::Birthday2, Name::Birthday2Month, Name::Birthday2Day, &BIRTHDAY),
+    (Name::Anniversary, Name::AnnivMonth, Name::AnnivDay, &ANNIVERSARY),
+];
+
+/// Get all greetings for `date` in the time zone `zone`, using the Greetings settings
+/// in `settings`.
+pub fn greetings<const N: usize>(date: Date, zone: &TimeZone, settings: &Menu<N>)
+    -> Vec<&'static Greeting, MAX_GREETINGS>
+{
+    let mut greetings = Vec::new();
+    for &(on, month, day, greeting) in PERSONAL.iter() {
+        if settings.setting_onoff(on) == Some(true) {
+            let month = settings.setting_numeric(month).unwrap() as u8;
+            let day = settings.setting_numeric(day).unwrap() as u8;
+            if let Ok(month) = Month::try_from(month) {
+                if (When::Fixed { month, day }).matches(date, zone) {
+                    greetings.push(greeting).ok();
+                }
+            }
+        }
+    }
+    if settings.setting_onoff(Name::Holidays) == Some(true) {
+        for (when, greeting) in CALENDAR.iter() {
+            if when.matches(date, zone) {
+                greetings.push(greeting).ok();
+            }
+        }
+    }
+    greetings
+}
+
+/// Compute the date of Easter Sunday in the Gregorian calendar.
+///
+/// Uses the anonymous Gregorian algorithm, also known as the Meeus/Jones/Butcher algorithm.
+pub fn easter(year: i32) -> Option<Date> {
+    let a = year.rem_euclid(19);
+    let (b, c) = (year.div_euclid(100), year.rem_euclid(100));
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    let month = Month::try_from(month as u8).ok()?;
+    Date::from_calendar_date(year, month, day as u8).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::ClockMenu, settings::MENU, tz::zone_rule};
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn easter_dates() {
+        assert_eq!(easter(2024), Some(date(2024, Month::March, 31)));
+        assert_eq!(easter(2025), Some(date(2025, Month::April, 20)));
+        assert_eq!(easter(2038), Some(date(2038, Month::April, 25)));
+        let good_friday = When::Easter { offset: -2 };
+        assert!(good_friday.matches(date(2025, Month::April, 18), &TimeZone::UTC));
+        assert!(!good_friday.matches(date(2025, Month::April, 20), &TimeZone::UTC));
+    }
+
+    #[test]
+    fn nth_weekday() {
+        let utc = TimeZone::UTC;
+        let last = When::NthWeekday { month: Month::March, weekday: Weekday::Sunday, n: -1 };
+        // March 2024 has five Sundays and March 2025 only four.
+        assert!(last.matches(date(2024, Month::March, 31), &utc));
+        assert!(!last.matches(date(2024, Month::March, 24), &utc));
+        assert!(last.matches(date(2025, Month::March, 30), &utc));
+        assert!(!last.matches(date(2025, Month::March, 29), &utc));
+        assert!(!last.matches(date(2025, Month::April, 27), &utc));
+
+        let second = When::NthWeekday { month: Month::May, weekday: Weekday::Monday, n: 2 };
+        assert!(second.matches(date(2024, Month::May, 13), &utc));
+        assert!(!second.matches(date(2024, Month::May, 6), &utc));
+    }
+
+    #[test]
+    fn fixed_dates_in_non_leap_years() {
+        let leap_day = When::Fixed { month: Month::February, day: 29 };
+        assert!(leap_day.matches(date(2024, Month::February, 29), &TimeZone::UTC));
+        assert!(!leap_day.matches(date(2024, Month::February, 28), &TimeZone::UTC));
+        assert!(leap_day.matches(date(2025, Month::February, 28), &TimeZone::UTC));
+    }
+
+    #[test]
+    fn birthdays_from_settings() {
+        let mut menu = ClockMenu::new(MENU);
+        menu.setting_set_onoff(Name::Holidays, false);
+        menu.setting_set_numeric(Name::Birthday1Month, 6);
+        menu.setting_set_numeric(Name::Birthday1Day, 14);
+        let birthday = date(2024, Month::June, 14);
+        assert!(greetings(birthday, &TimeZone::UTC, &menu).is_empty());
+
+        menu.setting_set_onoff(Name::Birthday1, true);
+        let todays = greetings(birthday, &TimeZone::UTC, &menu);
+        assert_eq!(todays.len(), 1);
+        assert_eq!(todays[0].lines[0], BIRTHDAY.lines[0]);
+        assert!(greetings(date(2024, Month::June, 15), &TimeZone::UTC, &menu).is_empty());
+    }
+
+    #[test]
+    fn clock_changes_follow_time_zone() {
+        let zone = |name| TimeZone::parse(zone_rule(name).unwrap()).unwrap();
+        let forward = When::ClocksChange { forward: true };
+        let back = When::ClocksChange { forward: false };
+        let london = zone(Name::London);
+        assert!(forward.matches(date(2024, Month::March, 31), &london));
+        assert!(back.matches(date(2024, Month::October, 27), &london));
+        assert!(!back.matches(date(2024, Month::March, 31), &london));
+
+        let new_york = zone(Name::NewYork);
+        assert!(forward.matches(date(2024, Month::March, 10), &new_york));
+        assert!(!forward.matches(date(2024, Month::March, 31), &new_york));
+
+        let sydney = zone(Name::Sydney);
+        assert!(forward.matches(date(2024, Month::October, 6), &sydney));
+        assert!(back.matches(date(2024, Month::April, 7), &sydney));
+
+        assert!(!forward.matches(date(2024, Month::March, 31), &TimeZone::UTC));
+    }
+}
@@ -0,0 +1,245 @@
+use heapless::Vec;
+
+/// Number of keys on the clock.
+pub const N_KEYS: usize = 6;
+
+/// Number of updates a key must be held for to give a long press, at 20 updates a second.
+pub const LONG_PRESS_TICKS: u16 = 20;
+
+/// Number of updates between repeats of a key held after a long press.
+pub const REPEAT_TICKS: u16 = 5;
+
+/// Number of updates a key is held for before its press is reported, during which
+/// pressing a second key makes a chord instead.
+pub const CHORD_TICKS: u16 = 3;
+
+/// Maximum number of events from one update: one per key, plus the press of a key
+/// released within `CHORD_TICKS`.
+pub const MAX_EVENTS: usize = N_KEYS + 1;
+
+/// Keys on the clock.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Key {
+    Back,
+    Qr,
+    Display,
+    Enter,
+    Left,
+    Right,
+}
+
+/// All keys, in the order their events are reported when several change at once.
+pub const KEYS: [Key; N_KEYS] = [Key::Back, Key::Qr, Key::Display, Key::Enter, Key::Left, Key::Right];
+
+/// The set of keys currently held down.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Keys(u8);
+
+impl Keys {
+    /// No keys held.
+    pub const fn new() -> Self {
+        Keys(0)
+    }
+
+    /// Return these keys with `key` held if `pressed`, or released otherwise.
+    pub const fn with(self, key: Key, pressed: bool) -> Self {
+        if pressed {
+            Keys(self.0 | 1 << key as u8)
+        } else {
+            Keys(self.0 & !(1 << key as u8))
+        }
+    }
+
+    pub const fn contains(&self, key: Key) -> bool {
+        self.0 & 1 << key as u8 != 0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// Gestures recognised from the keys held on each update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KeyEvent {
+    /// A key was pressed while no other key was held.
+    ///
+    /// Reported once the key has been held for `CHORD_TICKS` updates, or when it is
+    /// released if sooner, so that no press is reported for the first key of a chord.
+    Press(Key),
+    /// A key has been held for `LONG_PRESS_TICKS` updates.
+    LongPress(Key),
+    /// A key is still held, every `REPEAT_TICKS` updates after a long press.
+    Repeat(Key),
+    /// A key was released.
+    Release(Key),
+    /// The second key was pressed while the first was held.
+    ///
+    /// Neither key gives long presses or repeats until all keys are released.
+    Chord(Key, Key),
+}
+
+/// Recognises key gestures from the raw state of the keys.
+///
+/// Call `update()` regularly, at 20Hz for the default timings, with the keys currently held.
+#[derive(Copy, Clone, Debug)]
+pub struct Recogniser {
+    held: Keys,
+    ticks: [u16; N_KEYS],
+    chorded: bool,
+    pending: Option<Key>,
+    long_press: u16,
+    repeat: u16,
+}
+
+impl Recogniser {
+    /// Create a new Recogniser giving a long press after `long_press` updates,
+    /// then repeating every `repeat` updates.
+    ///
+    /// A `repeat` of 0 is treated as 1, repeating on every update.
+    pub const fn new(long_press: u16, repeat: u16) -> Self {
+        let repeat = if repeat == 0 { 1 } else { repeat };
+        Recogniser {
+            held: Keys::new(),
+            ticks: [0; N_KEYS],
+            chorded: false,
+            pending: None,
+            long_press,
+            repeat,
+        }
+    }
+
+    /// Update with the keys currently held, returning any events recognised.
+    pub fn update(&mut self, keys: Keys) -> Vec<KeyEvent, MAX_EVENTS> {
+        let mut events = Vec::new();
+        for &key in KEYS.iter() {
+            let ticks = &mut self.ticks[key as usize];
+            match (self.held.contains(key), keys.contains(key)) {
+                (false, true) => {
+                    *ticks = 0;
+                    let other = KEYS.iter().find(|&&k| self.held.contains(k));
+                    self.held = self.held.with(key, true);
+                    match other {
+                        Some(&other) => {
+                            self.chorded = true;
+                            self.pending = None;
+                            events.push(KeyEvent::Chord(other, key)).ok();
+                        },
+                        None => self.pending = Some(key),
+                    }
+                },
+                (true, true) if !self.chorded => {
+                    *ticks = ticks.saturating_add(1);
+                    if self.pending == Some(key) && *ticks >= CHORD_TICKS {
+                        self.pending = None;
+                        events.push(KeyEvent::Press(key)).ok();
+                    }
+                    if *ticks == self.long_press {
+                        events.push(KeyEvent::LongPress(key)).ok();
+                    } else if *ticks > self.long_press
+                              && (*ticks - self.long_press) % self.repeat == 0
+                    {
+                        events.push(KeyEvent::Repeat(key)).ok();
+                    }
+                },
+                (true, false) => {
+                    self.held = self.held.with(key, false);
+                    if self.pending == Some(key) {
+                        self.pending = None;
+                        events.push(KeyEvent::Press(key)).ok();
+                    }
+                    events.push(KeyEvent::Release(key)).ok();
+                },
+                _ => (),
+            }
+        }
+        if self.held.is_empty() {
+            self.chorded = false;
+        }
+        events
+    }
+}
+
+impl Default for Recogniser {
+    fn default() -> Self {
+        Self::new(LONG_PRESS_TICKS, REPEAT_TICKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Update `n` times with `keys` held, returning all the events recognised.
+    fn hold(recogniser: &mut Recogniser, keys: Keys, n: u16) -> Vec<KeyEvent, 64> {
+        let mut events = Vec::new();
+        for _ in 0..n {
+            for event in recogniser.update(keys) {
+                events.push(event).unwrap();
+            }
+        }
+        events
+    }
+
+    const LEFT: Keys = Keys::new().with(Key::Left, true);
+    const ENTER: Keys = Keys::new().with(Key::Enter, true);
+    const ENTER_BACK: Keys = ENTER.with(Key::Back, true);
+
+    #[test]
+    fn press_after_chord_window() {
+        let mut keys = Recogniser::default();
+        assert!(hold(&mut keys, ENTER, CHORD_TICKS).is_empty());
+        assert_eq!(&hold(&mut keys, ENTER, 1)[..], &[KeyEvent::Press(Key::Enter)]);
+        assert_eq!(&hold(&mut keys, Keys::new(), 1)[..], &[KeyEvent::Release(Key::Enter)]);
+    }
+
+    #[test]
+    fn short_press_on_release() {
+        let mut keys = Recogniser::default();
+        assert!(hold(&mut keys, ENTER, 1).is_empty());
+        assert_eq!(
+            &hold(&mut keys, Keys::new(), 1)[..],
+            &[KeyEvent::Press(Key::Enter), KeyEvent::Release(Key::Enter)]
+        );
+        assert!(hold(&mut keys, Keys::new(), 10).is_empty());
+    }
+
+    #[test]
+    fn long_press_and_repeat() {
+        let mut keys = Recogniser::default();
+        let events = hold(&mut keys, LEFT, LONG_PRESS_TICKS);
+        assert_eq!(&events[..], &[KeyEvent::Press(Key::Left)]);
+        assert_eq!(&hold(&mut keys, LEFT, 1)[..], &[KeyEvent::LongPress(Key::Left)]);
+        for _ in 0..3 {
+            assert!(hold(&mut keys, LEFT, REPEAT_TICKS - 1).is_empty());
+            assert_eq!(&hold(&mut keys, LEFT, 1)[..], &[KeyEvent::Repeat(Key::Left)]);
+        }
+        assert_eq!(&hold(&mut keys, Keys::new(), 1)[..], &[KeyEvent::Release(Key::Left)]);
+    }
+
+    #[test]
+    fn chord_without_press() {
+        let mut keys = Recogniser::default();
+        hold(&mut keys, ENTER, CHORD_TICKS - 1);
+        assert_eq!(&hold(&mut keys, ENTER_BACK, 1)[..], &[KeyEvent::Chord(Key::Enter, Key::Back)]);
+
+        // No long presses while chorded, even once only one key is left held.
+        assert!(hold(&mut keys, ENTER_BACK, 2 * LONG_PRESS_TICKS).is_empty());
+        assert_eq!(&hold(&mut keys, ENTER, 1)[..], &[KeyEvent::Release(Key::Back)]);
+        assert!(hold(&mut keys, ENTER, 2 * LONG_PRESS_TICKS).is_empty());
+        assert_eq!(&hold(&mut keys, Keys::new(), 1)[..], &[KeyEvent::Release(Key::Enter)]);
+
+        // Keys work normally again once all are released.
+        hold(&mut keys, LEFT, LONG_PRESS_TICKS);
+        assert_eq!(&hold(&mut keys, LEFT, 1)[..], &[KeyEvent::LongPress(Key::Left)]);
+    }
+
+    #[test]
+    fn zero_repeat_repeats_every_update() {
+        let mut keys = Recogniser::new(LONG_PRESS_TICKS, 0);
+        hold(&mut keys, LEFT, LONG_PRESS_TICKS);
+        assert_eq!(&hold(&mut keys, LEFT, 1)[..], &[KeyEvent::LongPress(Key::Left)]);
+        assert_eq!(&hold(&mut keys, LEFT, 1)[..], &[KeyEvent::Repeat(Key::Left)]);
+        assert_eq!(&hold(&mut keys, LEFT, 1)[..], &[KeyEvent::Repeat(Key::Left)]);
+    }
+}
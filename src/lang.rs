@@ -0,0 +1,44 @@
+use crate::Name;
+
+/// Languages for text shown on the clock.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Language {
+    English,
+    French,
+    Spanish,
+    Italian,
+    German,
+}
+
+/// Number of languages, each of which has a translation of every label.
+pub const N_LANGUAGES: usize = 5;
+
+/// Choices for the language setting, in the same order as `Language`.
+pub const LANGUAGE_NAMES: &[Name] = &[
+    Name::English, Name::French, Name::Spanish, Name::Italian, Name::German,
+];
+
+/// Short names of each month, starting with January.
+pub const MONTH_NAMES: [Name; 12] = [
+    Name::Jan, Name::Feb, Name::Mar, Name::Apr, Name::May, Name::Jun,
+    Name::Jul, Name::Aug, Name::Sep, Name::Oct, Name::Nov, Name::Dec,
+];
+
+/// Short names of each day of the week, starting with Monday.
+pub const WEEKDAY_NAMES: [Name; 7] = [
+    Name::Mon, Name::Tue, Name::Wed, Name::Thu, Name::Fri, Name::Sat, Name::Sun,
+];
+
+impl TryFrom<Name> for Language {
+    type Error = ();
+    fn try_from(name: Name) -> Result<Language, ()> {
+        match name {
+            Name::English   => Ok(Language::English),
+            Name::French    => Ok(Language::French),
+            Name::Spanish   => Ok(Language::Spanish),
+            Name::Italian   => Ok(Language::Italian),
+            Name::German    => Ok(Language::German),
+            _               => Err(()),
+        }
+    }
+}
@@ -1,10 +1,24 @@
 #![no_std]
 
+#[macro_use]
+mod schema;
+
+mod alarm;
 mod clock;
+mod face;
+mod format;
+mod greetings;
+mod keys;
+mod lang;
 mod map;
 mod menu;
-mod name;
 mod qr;
+mod settings;
+mod store;
+mod timer;
+mod tz;
 
-pub use name::Name;
-pub use clock::Clock;
+pub use settings::Name;
+pub use clock::{Clock, TimeError};
+pub use alarm::Event;
+pub use keys::{Key, KeyEvent, Keys, Recogniser};
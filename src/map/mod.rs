@@ -1,56 +1,59 @@
 use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
 use crate::{Name, clock::DateTime};
 
+pub mod route;
 pub mod shikoku;
 
+use route::RouteData;
+pub use route::Progress;
+
 #[derive(Copy, Clone, Debug)]
 pub enum Map {
     Shikoku,
 }
 
+/// Maps available in the menu.
+///
+/// New maps are added to the end so that saved menu choices remain valid.
 pub const MAP_NAMES: &[Name] = &[Name::NoMap, Name::Shikoku];
 
+/// Choices for which day to show once the last day of a route has passed.
+pub const ROUTE_END_NAMES: &[Name] = &[Name::Restart, Name::StayAtEnd];
+
 impl Map {
-    pub fn background(&self) -> Option<&'static [u8]> {
+    /// Get the data describing this map's route.
+    pub fn data(&self) -> &'static RouteData {
         match self {
-            Self::Shikoku       => Some(shikoku::IMAGE),
+            Self::Shikoku => &shikoku::SHIKOKU,
         }
     }
 
-    pub fn render<D>(&self, display: &mut D, local: &DateTime, frame: u16, day: u8, animate: bool)
+    pub fn background(&self) -> Option<&'static [u8]> {
+        Some(self.data().background)
+    }
+
+    pub fn render<D>(
+        &self, display: &mut D, local: &DateTime, frame: u16, day: u8, progress: Progress
+    )
         where D: DrawTarget<Color = Rgb888>
     {
-        match self {
-            Self::Shikoku   => shikoku::render(display, local, frame, day, animate),
-        }
+        route::render(self.data(), display, local, frame, day, progress);
     }
 
     pub fn name(&self) -> Name {
-        match self {
-            Self::Shikoku       => Name::Shikoku,
-        }
+        self.data().name
     }
 
     pub fn days(&self) -> usize {
-        match self {
-            Self::Shikoku       => shikoku::DAYS.len(),
-        }
+        self.data().days.len()
     }
 
     pub fn url(&self, day: u8) -> Option<&'static str> {
-        let day = day as usize;
-        match self {
-            Self::Shikoku if day < shikoku::URLS.len()      => Some(shikoku::URLS[day]),
-            _                                               => None,
-        }
+        self.data().urls.get(day as usize).copied()
     }
 
     pub fn image(&self, day: u8) -> Option<&'static [u8]> {
-        let day = day as usize;
-        match self {
-            Self::Shikoku if day < shikoku::IMAGES.len()    => Some(shikoku::IMAGES[day]),
-            _                                               => None,
-        }
+        self.data().images.get(day as usize).copied()
     }
 }
 
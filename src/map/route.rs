@@ -0,0 +1,173 @@
+use core::fmt::Write;
+use heapless::String;
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_6X9, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use crate::{Name, clock::DateTime};
+
+/// Description of a single walking route.
+///
+/// Every route is rendered by the same `render()` function, so adding a new
+/// route only requires supplying this data.
+#[derive(Debug)]
+pub struct RouteData {
+    /// Name of the route, as shown in the menu.
+    pub name: Name,
+
+    /// TGA image drawn behind the route.
+    pub background: &'static [u8],
+
+    /// Every pixel of the route, in the order they are walked.
+    pub route: &'static [(u8, u8)],
+
+    /// Index into `route` of the end of the walk on each day.
+    ///
+    /// Day 0 is the day before walking starts, and the final day is a rest day
+    /// which replays the entire route when animated.
+    pub days: &'static [u16],
+
+    /// Layers of landmarks along the route, such as temples or cathedrals.
+    ///
+    /// The number of landmarks reached so far in the first layer is displayed
+    /// alongside the walk day.
+    pub landmarks: &'static [Landmarks],
+
+    /// QR code URLs for each day.
+    pub urls: &'static [&'static str],
+
+    /// JPEG images for each day.
+    pub images: &'static [&'static [u8]],
+}
+
+/// A layer of landmarks drawn on top of a route.
+#[derive(Debug)]
+pub struct Landmarks {
+    /// Colour to draw landmarks once they have been reached.
+    pub color: Rgb888,
+
+    /// Landmark pixels, in the order they are reached.
+    ///
+    /// Each landmark must also be a pixel on the route.
+    pub points: &'static [(u8, u8)],
+
+    /// Number of landmarks reached by the end of each day.
+    pub per_day: &'static [u8],
+}
+
+/// How much of today's stage of the route to draw.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Progress {
+    /// Draw all of today's stage.
+    Complete,
+
+    /// Progressively draw today's stage, repeating roughly every three seconds,
+    /// with today's landmarks appearing as they are reached.
+    Animate,
+
+    /// Draw today's stage up to a walker who sets off at `start` o'clock and
+    /// arrives at `end` o'clock local time, lighting up landmarks as they pass.
+    Walk { start: u8, end: u8 },
+}
+
+/// Colour of the walker marker in `Progress::Walk`.
+const WALKER_COLOR: Rgb888 = Rgb888::new(255, 0, 255);
+
+/// Render `route` as of `day` into `display`.
+pub fn render<D>(
+    route: &RouteData, display: &mut D, local: &DateTime, frame: u16, day: u8, progress: Progress
+)
+    where D: DrawTarget<Color = Rgb888>
+{
+    let font = MonoTextStyle::new(&FONT_6X9, Rgb888::WHITE);
+    let style = TextStyleBuilder::new()
+        .alignment(Alignment::Right)
+        .baseline(Baseline::Bottom)
+        .build();
+    let mut s: String<2> = String::new();
+
+    // Find the day whose route and landmarks are drawn in full, if any,
+    // and the index of the last route pixel to draw today.
+    // The first and last days animate the entire route so far, and have no
+    // stage for the walker to walk.
+    let day = (day as usize).min(route.days.len() - 1);
+    let replay = day == 0 || day == route.days.len() - 1;
+    let (done, route_eidx) = match progress {
+        Progress::Complete => (Some(day), route.days[day] as usize),
+        Progress::Animate if replay => {
+            let mask = (route.days[day] + 1).next_power_of_two() - 1;
+            (None, u16::min(route.days[day], frame & mask) as usize)
+        },
+        Progress::Animate => {
+            let sidx = route.days[day - 1];
+            (Some(day - 1), u16::min(route.days[day], sidx + (frame & 63)) as usize)
+        },
+        Progress::Walk { .. } if replay => (Some(day), route.days[day] as usize),
+        Progress::Walk { start, end } => {
+            let sidx = route.days[day - 1] as usize;
+            let len = route.days[day] as usize - sidx;
+            (Some(day - 1), sidx + walked(local, start, end, len))
+        },
+    };
+    let route_sidx = done.map(|d| route.days[d] as usize).unwrap_or(0);
+
+    // Render route up til the start of today, followed by today's section.
+    for (x, y) in route.route[..route_eidx].iter() {
+        Pixel(Point::new(*x as i32, *y as i32), Rgb888::WHITE).draw(display).ok();
+    }
+
+    for layer in route.landmarks.iter() {
+        let sidx = done.map(|d| layer.per_day[d] as usize).unwrap_or(0);
+        let eidx = layer.per_day[day] as usize;
+
+        // Render already visited landmarks.
+        for (x, y) in layer.points[..sidx].iter() {
+            Pixel(Point::new(*x as i32, *y as i32), layer.color).draw(display).ok();
+        }
+
+        // Render today's landmarks once they've been visited by today's route section.
+        for (x, y) in layer.points[sidx..eidx].iter() {
+            if route.route[route_sidx..route_eidx].contains(&(*x, *y)) {
+                Pixel(Point::new(*x as i32, *y as i32), layer.color).draw(display).ok();
+            }
+        }
+    }
+
+    // Walker, flashing on top of the route and landmarks.
+    if let Progress::Walk { .. } = progress {
+        if route_eidx > 0 && frame & 16 == 0 {
+            let (x, y) = route.route[route_eidx - 1];
+            Pixel(Point::new(x as i32, y as i32), WALKER_COLOR).draw(display).ok();
+        }
+    }
+
+    // Walk day
+    write!(&mut s, "{:2}", day).ok();
+    Text::with_text_style(&s, Point::new(37, 64), font, style).draw(display).ok();
+    s.clear();
+
+    // Number of landmarks in the first layer
+    if let Some(layer) = route.landmarks.first() {
+        write!(&mut s, "{:2}", layer.per_day[day]).ok();
+        Text::with_text_style(&s, Point::new(55, 64), font, style).draw(display).ok();
+    }
+}
+
+/// Number of pixels out of `len` walked by `local` time, setting off at `start`
+/// o'clock and arriving at `end` o'clock.
+///
+/// The walk is complete all day if `end` is not after `start`.
+fn walked(local: &DateTime, start: u8, end: u8, len: usize) -> usize {
+    let now = local.hour() as u32 * 3600 + local.minute() as u32 * 60 + local.second() as u32;
+    let start = start as u32 * 3600;
+    let end = end as u32 * 3600;
+    if end <= start || now >= end {
+        len
+    } else if now <= start {
+        0
+    } else {
+        (len as u32 * (now - start) / (end - start)) as usize
+    }
+}
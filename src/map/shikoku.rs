@@ -1,14 +1,18 @@
-use core::fmt::Write;
-use heapless::String;
-use embedded_graphics::{
-    mono_font::{ascii::FONT_6X9, MonoTextStyle},
-    pixelcolor::Rgb888,
-    prelude::*,
-    text::{Alignment, Baseline, Text, TextStyleBuilder},
+use embedded_graphics::pixelcolor::Rgb888;
+use crate::Name;
+use super::route::{RouteData, Landmarks};
+
+pub static SHIKOKU: RouteData = RouteData {
+    name: Name::Shikoku,
+    background: IMAGE,
+    route: &ROUTE,
+    days: &DAYS,
+    landmarks: &[Landmarks { color: TEMPLE_COLOR, points: &TEMPLES, per_day: &N_TEMPLES }],
+    urls: URLS,
+    images: IMAGES,
 };
-use crate::clock::DateTime;
 
-pub static IMAGE: &[u8] = include_bytes!("../../artwork/shikoku/shikoku_base.tga");
+static IMAGE: &[u8] = include_bytes!("../../artwork/shikoku/shikoku_base.tga");
 
 static ROUTE: [(u8, u8); 292] = [
     (58, 15), (59, 15), (59, 16), (58, 16), (57, 16), (56, 16), (56, 17), (55, 16),
@@ -50,7 +54,7 @@ static ROUTE: [(u8, u8); 292] = [
     (53, 15), (52, 16), (52, 17), (53, 17),
 ];
 
-pub static DAYS: [u16; 52] = [
+static DAYS: [u16; 52] = [
     0, 6, 15, 18, 22, 29, 41, 47, 53, 56, 58, 62, 68, 78, 82, 90, 98, 105, 112, 119, 121, 127, 133,
     137, 143, 145, 150, 158, 162, 174, 182, 187, 191, 201, 205, 209, 214, 220, 226, 233, 237, 241,
     243, 247, 261, 272, 276, 278, 289, 291, 292, 292
@@ -78,7 +82,7 @@ static N_TEMPLES: [u8; 52] = [
 
 const TEMPLE_COLOR: Rgb888 = Rgb888::new(200, 50, 50);
 
-pub static URLS: &[&str] = &[
+static URLS: &[&str] = &[
     "HTTPS://TIMGREIG.CO.UK/2019/09/12/DAY-T",
     "HTTPS://TIMGREIG.CO.UK/2019/09/13/DAY-1",
     "HTTPS://TIMGREIG.CO.UK/2019/09/14/DAY-2",
@@ -133,7 +137,7 @@ pub static URLS: &[&str] = &[
     "HTTPS://TIMGREIG.CO.UK/2019/11/02/DAY-51",
 ];
 
-pub static IMAGES: &[&[u8]] = &[
+static IMAGES: &[&[u8]] = &[
     include_bytes!("../../artwork/shikoku/resized/0.jpg"),
     include_bytes!("../../artwork/shikoku/resized/1.jpg"),
     include_bytes!("../../artwork/shikoku/resized/2.jpg"),
@@ -187,60 +191,3 @@ pub static IMAGES: &[&[u8]] = &[
     include_bytes!("../../artwork/shikoku/resized/50.jpg"),
     include_bytes!("../../artwork/shikoku/resized/51.jpg"),
 ];
-
-pub fn render<D>(display: &mut D, _local: &DateTime, frame: u16, day: u8, animate: bool)
-    where D: DrawTarget<Color = Rgb888>
-{
-    let font = MonoTextStyle::new(&FONT_6X9, Rgb888::WHITE);
-    let style = TextStyleBuilder::new()
-        .alignment(Alignment::Right)
-        .baseline(Baseline::Bottom)
-        .build();
-    let mut s: String<2> = String::new();
-
-    // Get indices for pixels to draw today.
-    let day = (day as usize).min(DAYS.len());
-    let mask = if day == 51 { 511 } else { 63 };
-    let (route_sidx, temple_sidx) = if animate {
-        if day == 0 || day == 51 {
-            (0, 0)
-        } else {
-            (DAYS[day - 1] as usize, N_TEMPLES[day - 1] as usize)
-        }
-    } else {
-        (DAYS[day] as usize, N_TEMPLES[day] as usize)
-    };
-    let route_eidx = u16::min(DAYS[day], (route_sidx as u16) + (frame & mask)) as usize;
-    let temple_eidx = N_TEMPLES[day] as usize;
-
-    // Render route up til the start of today.
-    for (x, y) in ROUTE[..route_sidx].iter() {
-        Pixel(Point::new(*x as i32, *y as i32), Rgb888::WHITE).draw(display).ok();
-    }
-
-    // Render today's section of the route.
-    for (x, y) in ROUTE[route_sidx..route_eidx].iter() {
-        Pixel(Point::new(*x as i32, *y as i32), Rgb888::WHITE).draw(display).ok();
-    }
-
-    // Render already visited temples.
-    for (x, y) in TEMPLES[..temple_sidx].iter() {
-        Pixel(Point::new(*x as i32, *y as i32), TEMPLE_COLOR).draw(display).ok();
-    }
-
-    // Render today's temples once they've been visited by today's route section.
-    for (x, y) in TEMPLES[temple_sidx..temple_eidx].iter() {
-        if ROUTE[route_sidx..route_eidx].contains(&(*x, *y)) {
-            Pixel(Point::new(*x as i32, *y as i32), TEMPLE_COLOR).draw(display).ok();
-        }
-    }
-
-    // Walk day
-    write!(&mut s, "{:2}", day).ok();
-    Text::with_text_style(&s, Point::new(37, 64), font, style).draw(display).ok();
-    s.clear();
-
-    // Number of temples
-    write!(&mut s, "{:2}", N_TEMPLES[day as usize]).ok();
-    Text::with_text_style(&s, Point::new(55, 64), font, style).draw(display).ok();
-}
@@ -1,32 +1,59 @@
 use core::fmt::Write;
-use crate::name::Name;
+use time::Month;
+use crate::{Name, format::DateFormat, lang::Language};
 
 /// Menu structure.
 ///
-/// The menu consists of `N_CATEGORIES` categories, each of which contains `N_SETTINGS` settings.
+/// The menu is a tree of `Item`s, where each item is either a category containing
+/// further items, or a setting. Categories may be nested and may contain any number
+/// of items, so the tree is written out as nested `&'static [Item]` slices.
 ///
-/// The menu allows selecting a category, then selecting a setting in that category,
-/// and finally adjusting that setting. Settings may be disabled to prevent them from
-/// displaying, which allows categories to have fewer than `N_SETTINGS` actual settings.
+/// The menu allows selecting an item at the top level, entering categories to select
+/// items inside them, and finally adjusting a setting. Settings may be disabled to
+/// prevent them from displaying, and categories with no enabled settings are skipped.
 ///
 /// Each setting may be a boolean on/off switch, a numeric `i16` with a specified minimum
-/// and maximum value, or a choice from a selection of strings.
+/// and maximum value, a choice from a selection of strings, a UTC offset, or a composite
+/// time of day, date, or colour. Composite values are adjusted one field at a time, with
+/// ENTER moving on to the next field. Dates are shown and adjusted in the order of the
+/// date format set by `set_date_format()`.
 ///
-/// The menu state can be serialised to/from a slice of u16s, one per setting.
+/// Settings may have a `Rule` enabling them only while another setting has a particular
+/// value, which is applied when the menu is created and by `update_enabled()`.
+///
+/// Settings may also be actions, which have no value but are returned from `enter()`
+/// when selected, after a second ENTER to confirm if the action needs confirmation.
+///
+/// The current value of each setting is stored in a flat array of `N_SETTINGS` settings,
+/// in the order they appear in the tree; use `count()` to find `N_SETTINGS` for a tree.
+///
+/// Leaving a setting with `back()` restores the value it had when it was selected,
+/// and the menu closes itself after a period without any key presses; see `idle()`.
+///
+/// Each setting's value can be serialised to/from a u16 or a single byte.
 #[derive(Clone, Debug)]
-pub struct Menu<const N_CATEGORIES: usize, const N_SETTINGS: usize> {
-    index: usize,
+pub struct Menu<const N_SETTINGS: usize> {
+    items: &'static [Item],
+    settings: [Setting; N_SETTINGS],
+    path: [usize; MAX_DEPTH],
+    depth: usize,
     active: bool,
-    category_selected: bool,
-    categories: [Category<N_SETTINGS>; N_CATEGORIES],
+    setting_selected: bool,
+    field: usize,
+    saved: Option<Value>,
+    idle: u16,
+    date_format: DateFormat,
 }
 
+/// Maximum depth of nested categories.
+pub const MAX_DEPTH: usize = 4;
+
 #[derive(Copy, Clone, Debug)]
-pub struct Category<const N_SETTINGS: usize> {
-    name: Name,
-    index: usize,
-    setting_selected: bool,
-    settings: [Setting; N_SETTINGS],
+pub enum Item {
+    /// A category containing further items.
+    Category(Name, &'static [Item]),
+    /// A setting, with its default value.
+    Setting(Setting),
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -34,6 +61,33 @@ pub struct Setting {
     name: Name,
     enabled: bool,
     value: Value,
+    rule: Option<Rule>,
+}
+
+/// Rule for when a setting is enabled, depending on the value of another setting.
+///
+/// Rules are not chained, so a setting is still enabled by a rule on a disabled setting.
+#[derive(Copy, Clone, Debug)]
+pub enum Rule {
+    /// Enabled while the on/off setting is on.
+    On(Name),
+    /// Enabled while the on/off setting is off.
+    Off(Name),
+    /// Enabled while the choice setting has the second name chosen.
+    Is(Name, Name),
+}
+
+/// Name of a setting along with the type of its value, used to get its value with `Menu::get()`.
+///
+/// Types implementing this for each setting in the menu are generated by `settings!`.
+pub trait SettingKey {
+    type Value: FromSetting;
+    const NAME: Name;
+}
+
+/// Types which can be read from the value of a setting.
+pub trait FromSetting: Sized {
+    fn from_setting(setting: &Setting) -> Option<Self>;
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -48,128 +102,162 @@ pub enum Value {
         index: usize,
         choices: &'static [Name],
     },
+    /// UTC offset in minutes, adjusted in 15 minute steps from -12:00 to +14:00.
+    Offset(i16),
+    /// Time of day, with hour and minute fields.
+    Time {
+        hour: u8,
+        minute: u8,
+    },
+    /// Date from 2000 to 2099, with day, month, and year fields.
+    Date {
+        year: u16,
+        month: u8,
+        day: u8,
+    },
+    /// Colour, with red, green, and blue fields each from 0 to 15.
+    Color {
+        r: u8,
+        g: u8,
+        b: u8,
+    },
+    /// Action carried out when selected, which must be confirmed if `confirm` is set.
+    Action {
+        confirm: bool,
+    },
 }
 
-impl<const N_CATEGORIES: usize, const N_SETTINGS: usize> Menu<N_CATEGORIES, N_SETTINGS> {
-    pub const fn new(categories: [Category<N_SETTINGS>; N_CATEGORIES]) -> Self {
-        Self {
-            index: 0,
-            active: false,
-            category_selected: false,
-            categories,
-        }
-    }
-
-    pub fn active(&self) -> bool {
-        self.active
-    }
+/// Range and step of `Value::Offset`, in minutes.
+const OFFSET_MIN: i16 = -12 * 60;
+const OFFSET_MAX: i16 = 14 * 60;
+const OFFSET_STEP: i16 = 15;
 
-    pub fn category(&self, name: Name) -> Option<&Category<N_SETTINGS>> {
-        self.categories.iter().find(|c| c.name() == name)
-    }
+/// Range of years for `Value::Date`.
+const YEAR_MIN: u16 = 2000;
+const YEAR_MAX: u16 = 2099;
 
-    pub fn category_mut(&mut self, name: Name) -> Option<&mut Category<N_SETTINGS>> {
-        self.categories.iter_mut().find(|c| c.name() == name)
-    }
-
-    pub fn category_name(&self) -> Name {
-        self.categories[self.index].name()
-    }
-
-    pub fn category_selected(&self) -> bool {
-        self.category_selected
-    }
+/// Julian day of 1 January 2000, from which `Value::Date` is serialised.
+const JULIAN_2000: i32 = 2_451_545;
 
-    pub fn setting_name(&self) -> Name {
-        self.categories[self.index].setting_name()
-    }
-
-    pub fn setting_selected(&self) -> bool {
-        self.categories[self.index].setting_selected()
-    }
-
-    pub fn render_value<W: Write>(&self, w: W) -> core::fmt::Result {
-        self.categories[self.index].render_value(w)
+/// Count the settings in the tree of `items`.
+pub const fn count(items: &[Item]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    while i < items.len() {
+        n += match items[i] {
+            Item::Category(_, items) => count(items),
+            Item::Setting(_) => 1,
+        };
+        i += 1;
     }
+    n
+}
 
-    pub fn serialise(&self, mut data: &mut [u16]) {
-        for category in self.categories.iter() {
-            let n = category.serialise(data);
-            data = &mut data[n..];
+/// Copy the default settings from the tree of `items` into `settings`, starting at `n`,
+/// returning the new settings and the index after the last one copied.
+const fn flatten<const N_SETTINGS: usize>(
+    items: &[Item],
+    mut settings: [Setting; N_SETTINGS],
+    mut n: usize,
+) -> ([Setting; N_SETTINGS], usize) {
+    let mut i = 0;
+    while i < items.len() {
+        match items[i] {
+            Item::Category(_, items) => {
+                let flat = flatten(items, settings, n);
+                settings = flat.0;
+                n = flat.1;
+            },
+            Item::Setting(setting) => {
+                if n < N_SETTINGS {
+                    settings[n] = setting;
+                }
+                n += 1;
+            },
         }
+        i += 1;
     }
+    (settings, n)
+}
 
-    pub fn deserialise(&mut self, mut data: &[u16]) {
-        for category in self.categories.iter_mut() {
-            let n = category.deserialise(data);
-            data = &data[n..];
+/// Enable or disable each setting in `settings` which has a rule, using the current values.
+const fn apply_rules<const N_SETTINGS: usize>(
+    mut settings: [Setting; N_SETTINGS]
+) -> [Setting; N_SETTINGS] {
+    let mut i = 0;
+    while i < N_SETTINGS {
+        if let Some(rule) = settings[i].rule {
+            settings[i].enabled = rule_holds(&settings, rule);
         }
+        i += 1;
     }
+    settings
+}
 
-    pub fn inc(&mut self) -> bool {
-        if self.category_selected {
-            self.categories[self.index].inc()
-        } else {
-            if self.index == N_CATEGORIES - 1 {
-                self.index = 0;
-            } else {
-                self.index += 1;
-            }
-            false
+/// Check whether `rule` holds for the current values in `settings`.
+///
+/// Rules on settings which are missing or of the wrong kind never hold.
+const fn rule_holds<const N_SETTINGS: usize>(settings: &[Setting; N_SETTINGS], rule: Rule) -> bool {
+    let name = match rule {
+        Rule::On(name) | Rule::Off(name) | Rule::Is(name, _) => name,
+    };
+    let mut i = 0;
+    while i < N_SETTINGS {
+        if settings[i].name as u8 == name as u8 {
+            return match (rule, settings[i].value) {
+                (Rule::On(_), Value::OnOff(on)) => on,
+                (Rule::Off(_), Value::OnOff(on)) => !on,
+                (Rule::Is(_, choice), Value::Choice { index, choices }) => {
+                    index < choices.len() && choices[index] as u8 == choice as u8
+                },
+                _ => false,
+            };
         }
+        i += 1;
     }
+    false
+}
 
-    pub fn dec(&mut self) -> bool {
-        if self.category_selected {
-            self.categories[self.index].dec()
-        } else {
-            if self.index == 0 {
-                self.index = N_CATEGORIES - 1;
-            } else {
-                self.index -= 1;
-            }
-            false
+impl<const N_SETTINGS: usize> Menu<N_SETTINGS> {
+    /// Create a new menu from a tree of items, which must contain `N_SETTINGS` settings.
+    pub const fn new(items: &'static [Item]) -> Self {
+        Self {
+            items,
+            settings: apply_rules(flatten(items, [Setting::new_disabled(); N_SETTINGS], 0).0),
+            path: [0; MAX_DEPTH],
+            depth: 0,
+            active: false,
+            setting_selected: false,
+            field: 0,
+            saved: None,
+            idle: 0,
+            date_format: DateFormat::DayMonth,
         }
     }
 
-    pub fn enter(&mut self) {
-        if self.active {
-            if self.category_selected {
-                self.categories[self.index].enter();
-            } else {
-                self.category_selected = true;
-            }
-        } else {
-            self.active = true;
-            self.category_selected = false;
-        }
+    pub fn active(&self) -> bool {
+        self.active
     }
 
-    pub fn back(&mut self) {
-        if self.category_selected {
-            if self.setting_selected() {
-                self.categories[self.index].back();
-            } else {
-                self.category_selected = false;
-            }
-        } else {
-            self.active = false;
-        }
+    /// Get the names of the categories entered to reach the current item, outermost first.
+    pub fn path(&self) -> impl Iterator<Item = Name> + '_ {
+        (0..self.depth).map(move |d| match self.level(d)[self.path[d]] {
+            Item::Category(name, _) => name,
+            Item::Setting(setting) => setting.name(),
+        })
     }
-}
 
-impl<const N_SETTINGS: usize> Category<N_SETTINGS> {
-    pub const fn new(name: Name, settings: [Setting; N_SETTINGS]) -> Self {
-        Self {
-            name,
-            index: 0,
-            setting_selected: false,
-            settings,
+    /// Name of the current item, either a category or a setting.
+    pub fn item_name(&self) -> Name {
+        match self.item() {
+            Item::Category(name, _) => *name,
+            Item::Setting(setting) => setting.name(),
         }
     }
 
-    pub const fn name(&self) -> Name {
-        self.name
+    /// Whether the current item is a category, which is entered with `enter()`.
+    pub fn item_is_category(&self) -> bool {
+        matches!(self.item(), Item::Category(..))
     }
 
     pub fn setting(&self, name: Name) -> Option<&Setting> {
@@ -180,14 +268,38 @@ impl<const N_SETTINGS: usize> Category<N_SETTINGS> {
         self.settings.iter_mut().find(|s| s.name() == name)
     }
 
-    pub fn setting_name(&self) -> Name {
-        self.settings[self.index].name()
+    /// Get the value of the setting `key`.
+    ///
+    /// Panics if the setting is not in this menu.
+    pub fn get<K: SettingKey>(&self, _key: K) -> K::Value {
+        self.setting(K::NAME).and_then(K::Value::from_setting).unwrap()
+    }
+
+    /// Enable or disable each setting with a `Rule` according to the current values.
+    pub fn update_enabled(&mut self) {
+        self.settings = apply_rules(self.settings);
     }
 
     pub fn setting_selected(&self) -> bool {
         self.setting_selected
     }
 
+    /// Set the order in which the fields of dates are shown and adjusted.
+    pub fn set_date_format(&mut self, format: DateFormat) {
+        self.date_format = format;
+    }
+
+    /// Get the position and length of the field being adjusted within the rendered value,
+    /// if a composite setting is selected.
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        match self.item() {
+            Item::Setting(setting) if self.setting_selected => {
+                self.setting(setting.name())?.cursor(self.current_field(), self.date_format)
+            },
+            _ => None,
+        }
+    }
+
     pub fn setting_onoff(&self, name: Name) -> Option<bool> {
         self.setting(name).map(|s| s.onoff()).flatten()
     }
@@ -213,86 +325,264 @@ impl<const N_SETTINGS: usize> Category<N_SETTINGS> {
         self.setting_mut(name).map(|s| s.set_choice(v)).flatten()
     }
 
-    pub fn render_value<W: Write>(&self, w: W) -> core::fmt::Result {
-        self.settings[self.index].render(w)
+    pub fn setting_time(&self, name: Name) -> Option<(u8, u8)> {
+        self.setting(name).map(|s| s.time()).flatten()
+    }
+
+    pub fn setting_set_time(&mut self, name: Name, hour: u8, minute: u8) -> Option<()> {
+        self.setting_mut(name).map(|s| s.set_time(hour, minute)).flatten()
     }
 
-    pub fn setting_set_enabled(&mut self, name: Name, enabled: bool) -> Option<()> {
-        self.setting_mut(name).map(|s| s.set_enabled(enabled))
+    pub fn setting_set_date(&mut self, name: Name, year: u16, month: u8, day: u8) -> Option<()> {
+        self.setting_mut(name).map(|s| s.set_date(year, month, day)).flatten()
     }
 
     pub fn setting_set_max(&mut self, name: Name, max: i16) -> Option<()> {
         self.setting_mut(name).map(|s| s.set_max(max)).flatten()
     }
 
-    pub fn serialise(&self, data: &mut [u16]) -> usize {
-        let mut data = data.iter_mut();
-        let mut n_settings = 0;
-        for setting in self.settings.iter() {
-            if setting.name() != Name::Unused {
-                if let Some(word) = data.next() {
-                    *word = setting.serialise();
-                    n_settings += 1;
-                }
-            }
-        }
-        n_settings
+    /// Whether an action which needs confirming has been selected.
+    pub fn confirming(&self) -> bool {
+        self.setting_selected && self.current_setting().map(|s| s.action()) == Some(true)
     }
 
-    pub fn deserialise(&mut self, data: &[u16]) -> usize {
-        let mut data = data.iter();
-        let mut n_settings = 0;
-        for setting in self.settings.iter_mut() {
-            if setting.name() != Name::Unused {
-                if let Some(word) = data.next() {
-                    setting.deserialise(*word);
-                    n_settings += 1;
-                }
-            }
+    /// Render the value of the current setting in `language`, or nothing for a category.
+    pub fn render_value<W: Write>(&self, mut w: W, language: Language) -> core::fmt::Result {
+        if self.confirming() {
+            return w.write_str(Name::Sure.label(language));
+        }
+        match self.current_setting() {
+            Some(setting) => setting.render(w, language, self.date_format),
+            None => Ok(()),
         }
-        n_settings
     }
 
     pub fn inc(&mut self) -> bool {
+        self.inc_by(1)
+    }
+
+    /// Move to the next item, or increase the selected setting by `n` steps,
+    /// returning true if a setting was changed.
+    ///
+    /// On/off and choice settings only ever change by one step.
+    /// Steps of more than one stop at the end of the range rather than wrapping around.
+    pub fn inc_by(&mut self, n: u16) -> bool {
+        self.idle = 0;
         if self.setting_selected {
-            self.settings[self.index].inc();
-            true
-        } else {
-            if self.index == N_SETTINGS - 1 {
-                self.index = 0;
-            } else {
-                self.index += 1;
+            let field = self.current_field();
+            match self.current_setting_mut() {
+                Some(setting) if !setting.action() => {
+                    let single = matches!(setting.value(), Value::OnOff(_) | Value::Choice { .. });
+                    let n = if single { 1 } else { n };
+                    for _ in 0..n {
+                        if n > 1 && setting.at_limit(field, true) {
+                            break;
+                        }
+                        setting.inc(field);
+                    }
+                    true
+                },
+                _ => false,
             }
-            if !self.settings[self.index].enabled() {
-                self.inc();
+        } else {
+            let len = self.level(self.depth).len();
+            for _ in 0..len {
+                let index = &mut self.path[self.depth];
+                *index = if *index >= len - 1 { 0 } else { *index + 1 };
+                if self.enabled(self.item()) {
+                    break;
+                }
             }
             false
         }
     }
 
     pub fn dec(&mut self) -> bool {
+        self.dec_by(1)
+    }
+
+    /// Move to the previous item, or decrease the selected setting by `n` steps,
+    /// returning true if a setting was changed.
+    ///
+    /// On/off and choice settings only ever change by one step.
+    /// Steps of more than one stop at the end of the range rather than wrapping around.
+    pub fn dec_by(&mut self, n: u16) -> bool {
+        self.idle = 0;
         if self.setting_selected {
-            self.settings[self.index].dec();
-            true
+            let field = self.current_field();
+            match self.current_setting_mut() {
+                Some(setting) if !setting.action() => {
+                    let single = matches!(setting.value(), Value::OnOff(_) | Value::Choice { .. });
+                    let n = if single { 1 } else { n };
+                    for _ in 0..n {
+                        if n > 1 && setting.at_limit(field, false) {
+                            break;
+                        }
+                        setting.dec(field);
+                    }
+                    true
+                },
+                _ => false,
+            }
         } else {
-            if self.index == 0 {
-                self.index = N_SETTINGS - 1;
+            let len = self.level(self.depth).len();
+            for _ in 0..len {
+                let index = &mut self.path[self.depth];
+                *index = if *index == 0 { len - 1 } else { *index - 1 };
+                if self.enabled(self.item()) {
+                    break;
+                }
+            }
+            false
+        }
+    }
+
+    /// Open the menu, enter a category, or select or adjust the next field of a setting.
+    ///
+    /// Returns the name of an action setting when it is selected and confirmed.
+    pub fn enter(&mut self) -> Option<Name> {
+        self.idle = 0;
+        if !self.active {
+            self.active = true;
+            self.depth = 0;
+            self.setting_selected = false;
+        } else if let Item::Category(_, items) = self.item() {
+            if self.depth < MAX_DEPTH - 1 {
+                if let Some(index) = items.iter().position(|item| self.enabled(item)) {
+                    self.depth += 1;
+                    self.path[self.depth] = index;
+                }
+            }
+        } else if let Some(&Setting { name, value: Value::Action { confirm }, .. }) = self.current_setting() {
+            if confirm && !self.setting_selected {
+                self.setting_selected = true;
             } else {
-                self.index -= 1;
+                self.setting_selected = false;
+                return Some(name);
             }
-            if !self.settings[self.index].enabled() {
-                self.dec();
+        } else if !self.setting_selected {
+            self.setting_selected = true;
+            self.field = 0;
+            self.saved = self.current_setting().map(|s| *s.value());
+        } else {
+            let fields = self.current_setting().map(|s| s.fields()).unwrap_or(1);
+            if self.field + 1 < fields {
+                self.field += 1;
+            } else {
+                self.setting_selected = false;
+                self.saved = None;
             }
+        }
+        None
+    }
+
+    /// Close the menu from any depth, restoring any setting being adjusted,
+    /// returning true if a setting was changed.
+    pub fn close(&mut self) -> bool {
+        let restored = self.restore();
+        self.active = false;
+        restored
+    }
+
+    /// Leave the selected setting, restoring its value from before it was selected,
+    /// or leave the current category or the menu, returning true if a setting was changed.
+    pub fn back(&mut self) -> bool {
+        self.idle = 0;
+        if self.setting_selected {
+            return self.restore();
+        } else if self.depth > 0 {
+            self.depth -= 1;
+        } else {
+            self.active = false;
+        }
+        false
+    }
+
+    /// Reset the count of updates without any keys pressed, as when a key is held.
+    pub fn touch(&mut self) {
+        self.idle = 0;
+    }
+
+    /// Count one update without any keys pressed, closing the menu once there have
+    /// been `timeout` such updates in a row, or never if `timeout` is 0.
+    ///
+    /// Returns true if a setting being adjusted was restored when closing.
+    pub fn idle(&mut self, timeout: u16) -> bool {
+        if !self.active || timeout == 0 {
+            return false;
+        }
+        self.idle = self.idle.saturating_add(1);
+        if self.idle >= timeout {
+            self.idle = 0;
+            self.close()
+        } else {
             false
         }
     }
 
-    pub fn enter(&mut self) {
-        self.setting_selected = !self.setting_selected;
+    /// Deselect the selected setting, restoring its saved value,
+    /// returning true if the value was changed.
+    fn restore(&mut self) -> bool {
+        let selected = core::mem::replace(&mut self.setting_selected, false);
+        match (self.saved.take(), self.current_setting_mut()) {
+            (Some(value), Some(setting)) if selected => {
+                let changed = setting.serialise() != Setting { value, ..*setting }.serialise();
+                setting.value = value;
+                changed
+            },
+            _ => false,
+        }
+    }
+
+    /// Get the items in the category entered at `depth`, where depth 0 is the top level.
+    fn level(&self, depth: usize) -> &'static [Item] {
+        let mut items = self.items;
+        for &index in self.path[..depth].iter() {
+            if let Item::Category(_, inner) = items[index] {
+                items = inner;
+            }
+        }
+        items
+    }
+
+    /// Get the current item.
+    fn item(&self) -> &'static Item {
+        &self.level(self.depth)[self.path[self.depth]]
     }
 
-    pub fn back(&mut self) {
-        self.setting_selected = false;
+    /// Get the field of the selected setting being adjusted, which for dates depends on
+    /// the date format since their fields are adjusted in the order they are shown.
+    fn current_field(&self) -> usize {
+        match self.current_setting().map(|s| s.value) {
+            Some(Value::Date { .. }) => {
+                date_fields(self.date_format).get(self.field).copied().unwrap_or(self.field)
+            },
+            _ => self.field,
+        }
+    }
+
+    /// Get the current setting, if the current item is a setting.
+    fn current_setting(&self) -> Option<&Setting> {
+        match self.item() {
+            Item::Category(..) => None,
+            Item::Setting(setting) => self.setting(setting.name()),
+        }
+    }
+
+    /// Get the current setting, if the current item is a setting.
+    fn current_setting_mut(&mut self) -> Option<&mut Setting> {
+        match self.item() {
+            Item::Category(..) => None,
+            Item::Setting(setting) => self.setting_mut(setting.name()),
+        }
+    }
+
+    /// Check whether `item` is an enabled setting or a category containing one.
+    fn enabled(&self, item: &Item) -> bool {
+        match item {
+            Item::Category(_, items) => items.iter().any(|item| self.enabled(item)),
+            Item::Setting(setting) => self.setting(setting.name()).map(|s| s.enabled()) == Some(true),
+        }
     }
 }
 
@@ -302,9 +592,16 @@ impl Setting {
             name,
             enabled,
             value,
+            rule: None,
         }
     }
 
+    /// Return this setting enabled only while `rule` holds.
+    pub const fn when(mut self, rule: Rule) -> Self {
+        self.rule = Some(rule);
+        self
+    }
+
     pub const fn new_onoff(name: Name, enabled: bool, value: bool) -> Self {
         Self::new(name, enabled, Value::OnOff(value))
     }
@@ -328,6 +625,27 @@ impl Setting {
         Self::new(name, enabled, Value::Choice { index, choices })
     }
 
+    pub const fn new_offset(name: Name, enabled: bool, minutes: i16) -> Self {
+        Self::new(name, enabled, Value::Offset(minutes))
+    }
+
+    pub const fn new_time(name: Name, enabled: bool, hour: u8, minute: u8) -> Self {
+        Self::new(name, enabled, Value::Time { hour, minute })
+    }
+
+    pub const fn new_date(name: Name, enabled: bool, year: u16, month: u8, day: u8) -> Self {
+        Self::new(name, enabled, Value::Date { year, month, day })
+    }
+
+    /// Create a colour setting, with each component from 0 to 15.
+    pub const fn new_color(name: Name, enabled: bool, r: u8, g: u8, b: u8) -> Self {
+        Self::new(name, enabled, Value::Color { r, g, b })
+    }
+
+    pub const fn new_action(name: Name, enabled: bool, confirm: bool) -> Self {
+        Self::new(name, enabled, Value::Action { confirm })
+    }
+
     pub const fn new_disabled() -> Self {
         Self::new(Name::Unused, false, Value::OnOff(false))
     }
@@ -336,7 +654,6 @@ impl Setting {
         self.name
     }
 
-    #[allow(unused)]
     pub fn value(&self) -> &Value {
         &self.value
     }
@@ -398,15 +715,198 @@ impl Setting {
         None
     }
 
-    pub fn render<W: Write>(&self, mut w: W) -> core::fmt::Result {
+    pub fn offset(&self) -> Option<i16> {
+        if let Value::Offset(minutes) = self.value {
+            Some(minutes)
+        } else {
+            None
+        }
+    }
+
+    pub fn time(&self) -> Option<(u8, u8)> {
+        if let Value::Time { hour, minute } = self.value {
+            Some((hour, minute))
+        } else {
+            None
+        }
+    }
+
+    pub fn set_time(&mut self, hour: u8, minute: u8) -> Option<()> {
+        if let (Value::Time { .. }, true) = (self.value, hour < 24 && minute < 60) {
+            self.value = Value::Time { hour, minute };
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    pub fn date(&self) -> Option<(u16, u8, u8)> {
+        if let Value::Date { year, month, day } = self.value {
+            Some((year, month, day))
+        } else {
+            None
+        }
+    }
+
+    pub fn set_date(&mut self, year: u16, month: u8, day: u8) -> Option<()> {
+        if let (Value::Date { .. }, true) = (self.value, valid_date(year, month, day)) {
+            self.value = Value::Date { year, month, day };
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Get a colour as 8-bit red, green, and blue components.
+    pub fn color(&self) -> Option<(u8, u8, u8)> {
+        if let Value::Color { r, g, b } = self.value {
+            Some((r * 17, g * 17, b * 17))
+        } else {
+            None
+        }
+    }
+
+    /// Whether this setting is an action, which has no value.
+    pub fn action(&self) -> bool {
+        matches!(self.value, Value::Action { .. })
+    }
+
+    /// Number of fields adjusted separately, which is 1 except for composite values.
+    pub fn fields(&self) -> usize {
+        match self.value {
+            Value::Time { .. } => 2,
+            Value::Date { .. } | Value::Color { .. } => 3,
+            _ => 1,
+        }
+    }
+
+    /// Get `field` of a composite value.
+    pub fn field(&self, field: usize) -> Option<u16> {
+        match (self.value, field) {
+            (Value::Time { hour, .. }, 0) => Some(hour as u16),
+            (Value::Time { minute, .. }, 1) => Some(minute as u16),
+            (Value::Date { day, .. }, 0) => Some(day as u16),
+            (Value::Date { month, .. }, 1) => Some(month as u16),
+            (Value::Date { year, .. }, 2) => Some(year),
+            (Value::Color { r, .. }, 0) => Some(r as u16),
+            (Value::Color { g, .. }, 1) => Some(g as u16),
+            (Value::Color { b, .. }, 2) => Some(b as u16),
+            _ => None,
+        }
+    }
+
+    /// Set `field` of a composite value.
+    ///
+    /// Returns None, leaving the value unchanged, if `v` is out of range for the field.
+    /// Changing the month or year of a date limits the day to the days in that month.
+    pub fn set_field(&mut self, field: usize, v: u16) -> Option<()> {
+        let (min, max) = self.field_range(field)?;
+        if v < min || v > max {
+            return None;
+        }
+        match (&mut self.value, field) {
+            (Value::Time { hour, .. }, 0) => *hour = v as u8,
+            (Value::Time { minute, .. }, 1) => *minute = v as u8,
+            (Value::Date { day, .. }, 0) => *day = v as u8,
+            (Value::Date { month, .. }, 1) => *month = v as u8,
+            (Value::Date { year, .. }, 2) => *year = v,
+            (Value::Color { r, .. }, 0) => *r = v as u8,
+            (Value::Color { g, .. }, 1) => *g = v as u8,
+            (Value::Color { b, .. }, 2) => *b = v as u8,
+            _ => return None,
+        }
+        if let Value::Date { year, month, day } = &mut self.value {
+            *day = (*day).min(days_in_month(*year, *month));
+        }
+        Some(())
+    }
+
+    /// Get the position and length of `field` in the value rendered with `date_format`,
+    /// or None if the value is not composite.
+    pub fn cursor(&self, field: usize, date_format: DateFormat) -> Option<(usize, usize)> {
+        match (self.value, field) {
+            (Value::Time { .. }, 0) => Some((0, 2)),
+            (Value::Time { .. }, 1) => Some((3, 2)),
+            (Value::Date { .. }, 0..=2) => {
+                let position = date_fields(date_format).iter().position(|&f| f == field)?;
+                let cursors = match date_format {
+                    DateFormat::Iso => [(0, 4), (5, 2), (8, 2)],
+                    _ => [(0, 2), (3, 2), (6, 4)],
+                };
+                Some(cursors[position])
+            },
+            (Value::Color { .. }, 0) => Some((1, 2)),
+            (Value::Color { .. }, 1) => Some((3, 2)),
+            (Value::Color { .. }, 2) => Some((5, 2)),
+            _ => None,
+        }
+    }
+
+    /// Get the minimum and maximum of `field` of a composite value.
+    fn field_range(&self, field: usize) -> Option<(u16, u16)> {
+        match (self.value, field) {
+            (Value::Time { .. }, 0) => Some((0, 23)),
+            (Value::Time { .. }, 1) => Some((0, 59)),
+            (Value::Date { year, month, .. }, 0) => Some((1, days_in_month(year, month) as u16)),
+            (Value::Date { .. }, 1) => Some((1, 12)),
+            (Value::Date { .. }, 2) => Some((YEAR_MIN, YEAR_MAX)),
+            (Value::Color { .. }, 0..=2) => Some((0, 15)),
+            _ => None,
+        }
+    }
+
+    /// Check whether the value, or `field` of a composite value, is at the top of its
+    /// range if `up`, or otherwise at the bottom, so that stepping it would wrap around.
+    fn at_limit(&self, field: usize, up: bool) -> bool {
+        let (v, min, max) = match self.value {
+            Value::Numeric { min, max, val } => (val as i32, min as i32, max as i32),
+            Value::Offset(m) => (m as i32, OFFSET_MIN as i32, OFFSET_MAX as i32),
+            _ => match (self.field(field), self.field_range(field)) {
+                (Some(v), Some((min, max))) => (v as i32, min as i32, max as i32),
+                _ => return false,
+            },
+        };
+        if up { v >= max } else { v <= min }
+    }
+
+    /// Step `field` of a composite value up or down by one, wrapping around.
+    fn step_field(&mut self, field: usize, up: bool) {
+        if let (Some(v), Some((min, max))) = (self.field(field), self.field_range(field)) {
+            let v = match up {
+                true => if v >= max { min } else { v + 1 },
+                false => if v <= min { max } else { v - 1 },
+            };
+            self.set_field(field, v);
+        }
+    }
+
+    /// Render the value in `language`, with dates in the order of `date_format`.
+    pub fn render<W: Write>(
+        &self, mut w: W, language: Language, date_format: DateFormat
+    ) -> core::fmt::Result {
         match &self.value {
-            Value::OnOff(b) => if *b { write!(w, "On") } else { write!(w, "Off") },
+            Value::OnOff(b) => {
+                w.write_str(if *b { Name::On } else { Name::Off }.label(language))
+            },
             Value::Numeric { val, .. } => write!(w, "{}", val),
-            Value::Choice { index, choices } => write!(w, "{}", choices[*index]),
+            Value::Choice { index, choices } => w.write_str(choices[*index].label(language)),
+            Value::Offset(m) => {
+                let sign = if *m < 0 { '-' } else { '+' };
+                write!(w, "{}{:02}:{:02}", sign, m.abs() / 60, m.abs() % 60)
+            },
+            Value::Time { hour, minute } => write!(w, "{:02}:{:02}", hour, minute),
+            Value::Date { year, month, day } => match date_format {
+                DateFormat::DayMonth => write!(w, "{:02}/{:02}/{}", day, month, year),
+                DateFormat::MonthDay => write!(w, "{:02}/{:02}/{}", month, day, year),
+                DateFormat::Iso => write!(w, "{}-{:02}-{:02}", year, month, day),
+            },
+            Value::Color { r, g, b } => write!(w, "#{:02X}{:02X}{:02X}", r * 17, g * 17, b * 17),
+            Value::Action { .. } => w.write_str(Name::PressEnter.label(language)),
         }
     }
 
-    pub fn inc(&mut self) {
+    /// Adjust the value, or `field` of a composite value.
+    pub fn inc(&mut self, field: usize) {
         match &mut self.value {
             Value::OnOff(b) => *b = !*b,
             Value::Numeric { min, max, val } => {
@@ -423,10 +923,22 @@ impl Setting {
                     *index += 1;
                 }
             }
+            Value::Offset(m) => {
+                if *m >= OFFSET_MAX {
+                    *m = OFFSET_MIN;
+                } else {
+                    *m += OFFSET_STEP;
+                }
+            }
+            Value::Time { .. } | Value::Date { .. } | Value::Color { .. } => {
+                self.step_field(field, true);
+            }
+            Value::Action { .. } => (),
         }
     }
 
-    pub fn dec(&mut self) {
+    /// Adjust the value, or `field` of a composite value.
+    pub fn dec(&mut self, field: usize) {
         match &mut self.value {
             Value::OnOff(b) => *b = !*b,
             Value::Numeric { min, max, val } => {
@@ -443,6 +955,17 @@ impl Setting {
                     *index -= 1;
                 }
             }
+            Value::Offset(m) => {
+                if *m <= OFFSET_MIN {
+                    *m = OFFSET_MAX;
+                } else {
+                    *m -= OFFSET_STEP;
+                }
+            }
+            Value::Time { .. } | Value::Date { .. } | Value::Color { .. } => {
+                self.step_field(field, false);
+            }
+            Value::Action { .. } => (),
         }
     }
 
@@ -451,14 +974,77 @@ impl Setting {
             Value::OnOff(b) => b as u16,
             Value::Numeric { val, .. } => val as u16,
             Value::Choice { index, .. } => index as u16,
+            Value::Offset(m) => m as u16,
+            Value::Time { hour, minute } => hour as u16 * 60 + minute as u16,
+            Value::Date { year, month, day } => {
+                let date = Month::try_from(month).ok()
+                    .and_then(|month| time::Date::from_calendar_date(year as i32, month, day).ok());
+                date.map(|d| (d.to_julian_day() - JULIAN_2000) as u16).unwrap_or(0)
+            },
+            Value::Color { r, g, b } => (r as u16) << 8 | (g as u16) << 4 | b as u16,
+            Value::Action { .. } => 0,
         }
     }
 
-    pub fn deserialise(&mut self, word: u16) {
+    /// Set the value from a word previously returned by `serialise()`.
+    ///
+    /// Returns None, leaving the value unchanged, if the word is not a valid
+    /// value for this setting.
+    pub fn deserialise(&mut self, word: u16) -> Option<()> {
         match &mut self.value {
-            Value::OnOff(b) => *b = word != 0,
-            Value::Numeric { val, .. } => *val = word as i16,
-            Value::Choice { index, .. } => *index = word as usize,
+            Value::OnOff(b) if word <= 1 => *b = word != 0,
+            Value::Numeric { min, max, val } if (*min..=*max).contains(&(word as i16)) => {
+                *val = word as i16;
+            },
+            Value::Choice { index, choices } if (word as usize) < choices.len() => {
+                *index = word as usize;
+            },
+            Value::Offset(m) if (OFFSET_MIN..=OFFSET_MAX).contains(&(word as i16))
+                                && (word as i16) % OFFSET_STEP == 0 => {
+                *m = word as i16;
+            },
+            Value::Time { hour, minute } if word < 24 * 60 => {
+                *hour = (word / 60) as u8;
+                *minute = (word % 60) as u8;
+            },
+            Value::Date { year, month, day } => {
+                let date = time::Date::from_julian_day(JULIAN_2000 + word as i32).ok()
+                    .filter(|d| d.year() <= YEAR_MAX as i32)?;
+                *year = date.year() as u16;
+                *month = date.month() as u8;
+                *day = date.day();
+            },
+            Value::Color { r, g, b } if word < 0x1000 => {
+                *r = (word >> 8) as u8;
+                *g = (word >> 4 & 0xf) as u8;
+                *b = (word & 0xf) as u8;
+            },
+            Value::Action { .. } if word == 0 => (),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    /// Serialise to a single byte, or None if the value does not fit in one.
+    ///
+    /// Numeric values must be in 0 to 255, offsets are stored in steps,
+    /// and composite values never fit.
+    pub fn serialise_byte(&self) -> Option<u8> {
+        match self.value {
+            Value::Offset(m) => Some((m / OFFSET_STEP) as i8 as u8),
+            Value::Time { .. } | Value::Date { .. } | Value::Color { .. } => None,
+            _ => u8::try_from(self.serialise()).ok(),
+        }
+    }
+
+    /// Set the value from a byte previously returned by `serialise_byte()`.
+    ///
+    /// Returns None, leaving the value unchanged, if the byte is not a valid
+    /// value for this setting.
+    pub fn deserialise_byte(&mut self, byte: u8) -> Option<()> {
+        match self.value {
+            Value::Offset(_) => self.deserialise((byte as i8 as i16 * OFFSET_STEP) as u16),
+            _ => self.deserialise(byte as u16),
         }
     }
 
@@ -477,8 +1063,245 @@ impl Setting {
     pub fn enabled(&self) -> bool {
         self.enabled
     }
+}
+
+impl FromSetting for bool {
+    fn from_setting(setting: &Setting) -> Option<Self> {
+        setting.onoff()
+    }
+}
+
+impl FromSetting for i16 {
+    fn from_setting(setting: &Setting) -> Option<Self> {
+        setting.numeric().or_else(|| setting.offset())
+    }
+}
+
+impl FromSetting for Name {
+    fn from_setting(setting: &Setting) -> Option<Self> {
+        setting.choice()
+    }
+}
+
+impl FromSetting for (u8, u8) {
+    fn from_setting(setting: &Setting) -> Option<Self> {
+        setting.time()
+    }
+}
+
+impl FromSetting for (u16, u8, u8) {
+    fn from_setting(setting: &Setting) -> Option<Self> {
+        setting.date()
+    }
+}
+
+impl FromSetting for (u8, u8, u8) {
+    fn from_setting(setting: &Setting) -> Option<Self> {
+        setting.color()
+    }
+}
+
+/// Get the fields of `Value::Date` in the order they are shown in `format`.
+const fn date_fields(format: DateFormat) -> [usize; 3] {
+    match format {
+        DateFormat::DayMonth => [0, 1, 2],
+        DateFormat::MonthDay => [1, 0, 2],
+        DateFormat::Iso => [2, 1, 0],
+    }
+}
+
+/// Get the number of days in `month` of `year`, or 31 if the month is invalid.
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match Month::try_from(month) {
+        Ok(month) => time::util::days_in_year_month(year as i32, month),
+        Err(_) => 31,
+    }
+}
+
+/// Check whether a date is valid and within the range of `Value::Date`.
+fn valid_date(year: u16, month: u8, day: u8) -> bool {
+    (YEAR_MIN..=YEAR_MAX).contains(&year) && (1..=12).contains(&month)
+        && day >= 1 && day <= days_in_month(year, month)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: &[Item] = &[
+        Item::Setting(Setting::new_numeric(Name::Brightness, true, 0, 10, 5)),
+        Item::Setting(Setting::new_time(Name::Alarm1Time, true, 7, 30)),
+    ];
+
+    /// An open menu with its first setting, Brightness, selected.
+    fn selected() -> Menu<2> {
+        let mut menu = Menu::new(ITEMS);
+        menu.enter();
+        menu.enter();
+        assert!(menu.setting_selected());
+        menu
+    }
+
+    #[test]
+    fn back_restores_value() {
+        let mut menu = selected();
+        menu.inc();
+        menu.inc();
+        menu.inc();
+        menu.dec();
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(7));
+        assert!(menu.back());
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(5));
+        assert!(menu.active() && !menu.setting_selected());
+
+        // Nothing to restore if the value ends up unchanged.
+        menu.enter();
+        menu.inc();
+        menu.dec();
+        assert!(!menu.back());
+    }
+
+    #[test]
+    fn enter_keeps_value() {
+        let mut menu = selected();
+        menu.inc();
+        menu.enter();
+        assert!(!menu.back());
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(6));
+    }
+
+    #[test]
+    fn idle_closes_after_timeout() {
+        let mut menu = Menu::<2>::new(ITEMS);
+        menu.enter();
+        for _ in 0..9 {
+            assert!(!menu.idle(10));
+            assert!(menu.active());
+        }
+        assert!(!menu.idle(10));
+        assert!(!menu.active());
+
+        // Closed menus stay closed.
+        assert!(!menu.idle(10));
+        assert!(!menu.active());
+    }
+
+    #[test]
+    fn touch_restarts_timeout() {
+        let mut menu = Menu::<2>::new(ITEMS);
+        menu.enter();
+        for _ in 0..9 {
+            menu.idle(10);
+        }
+        menu.touch();
+        for _ in 0..9 {
+            menu.idle(10);
+        }
+        assert!(menu.active());
+        menu.idle(10);
+        assert!(!menu.active());
+    }
+
+    #[test]
+    fn idle_reverts_half_edited_value() {
+        let mut menu = Menu::<2>::new(ITEMS);
+        menu.enter();
+        menu.inc();
+        menu.enter();
+        menu.inc();
+        menu.enter();
+        menu.dec();
+        assert_eq!(menu.setting_time(Name::Alarm1Time), Some((8, 29)));
+        for _ in 0..4 {
+            assert!(!menu.idle(5));
+        }
+        assert!(menu.idle(5));
+        assert!(!menu.active());
+        assert_eq!(menu.setting_time(Name::Alarm1Time), Some((7, 30)));
+    }
+
+    #[test]
+    fn idle_zero_never_closes() {
+        let mut menu = selected();
+        menu.inc();
+        for _ in 0..u16::MAX as u32 + 10 {
+            assert!(!menu.idle(0));
+        }
+        assert!(menu.active());
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(6));
+    }
+
+    #[test]
+    fn dates_follow_date_format() {
+        const DATE: &[Item] = &[Item::Setting(Setting::new_date(Name::Date, true, 2024, 3, 9))];
+        let render = |menu: &Menu<1>| {
+            let mut s: heapless::String<16> = heapless::String::new();
+            menu.render_value(&mut s, Language::English).unwrap();
+            s
+        };
+
+        let mut menu = Menu::<1>::new(DATE);
+        menu.enter();
+        menu.enter();
+        assert_eq!(render(&menu), "09/03/2024");
+        assert_eq!(menu.cursor(), Some((0, 2)));
+
+        // Month first, so the first field adjusted is the month.
+        menu.set_date_format(DateFormat::MonthDay);
+        assert_eq!(render(&menu), "03/09/2024");
+        menu.inc();
+        assert_eq!(render(&menu), "04/09/2024");
+        menu.enter();
+        assert_eq!(menu.cursor(), Some((3, 2)));
+        menu.inc();
+        assert_eq!(render(&menu), "04/10/2024");
+
+        // Year first for ISO dates.
+        menu.set_date_format(DateFormat::Iso);
+        menu.back();
+        menu.enter();
+        assert_eq!(render(&menu), "2024-03-09");
+        assert_eq!(menu.cursor(), Some((0, 4)));
+        menu.inc();
+        menu.enter();
+        assert_eq!(menu.cursor(), Some((5, 2)));
+        menu.inc();
+        menu.enter();
+        assert_eq!(menu.cursor(), Some((8, 2)));
+        menu.inc();
+        assert_eq!(render(&menu), "2025-04-10");
+    }
+
+    #[test]
+    fn large_steps_stop_at_range() {
+        let mut menu = selected();
+        assert!(menu.inc_by(10));
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(10));
+        assert!(menu.inc_by(10));
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(10));
+        assert!(menu.dec_by(9));
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(1));
+        assert!(menu.dec_by(10));
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(0));
+
+        // Single steps still wrap around.
+        assert!(menu.dec());
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(10));
+    }
 
-    pub fn set_enabled(&mut self, enabled: bool) {
-        self.enabled = enabled
+    #[test]
+    fn large_steps_stop_at_field_range() {
+        let mut menu = Menu::<2>::new(ITEMS);
+        menu.enter();
+        menu.inc();
+        menu.enter();
+        assert!(menu.inc_by(10));
+        assert!(menu.inc_by(10));
+        assert_eq!(menu.setting_time(Name::Alarm1Time), Some((23, 30)));
+        menu.enter();
+        assert!(menu.inc_by(10));
+        assert!(menu.inc_by(10));
+        assert!(menu.inc_by(10));
+        assert_eq!(menu.setting_time(Name::Alarm1Time), Some((23, 59)));
     }
 }
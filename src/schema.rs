@@ -0,0 +1,199 @@
+/// Declare every setting in the menu once, along with all other names.
+///
+/// The menu tree is written as a list of items. Each category is written as its name,
+/// label, and its items in braces:
+///
+/// ```text
+/// Display "Display" { ... }
+/// ```
+///
+/// Each setting is written as its name, its ID if it is saved, its label, the kind of
+/// value with the arguments to its `Setting` constructor after the name and enabled flag,
+/// and optionally a rule for when it is enabled:
+///
+/// ```text
+/// DimBrightness #15 "Dim bright": Numeric(0, 10, 8) if DimAtNight;
+/// Date "Date": Date(2000, 1, 1) if (!GPSTime);
+/// UTCOffset #3 "UTC offset": Offset(0) if (TimeZone == Manual);
+/// ```
+///
+/// Settings are saved tagged with their IDs, and settings without an ID are not saved.
+/// IDs must never be changed or reused, so that saved settings remain valid when the menu
+/// is rearranged. New settings should be given the next unused ID.
+///
+/// Each label is either one string used in every language, or a list of translations
+/// in the order of `Language`:
+///
+/// ```text
+/// Night ["Night", "Nuit", "Noche", "Notte", "Nacht"] { ... }
+/// ```
+///
+/// The label may be left out of a setting or category which shares a name with an
+/// earlier category or setting.
+///
+/// Finally all other names, such as choices and settings no longer in the menu, are
+/// listed with their labels, and with their IDs for settings which were saved.
+///
+/// This generates the `Name` enum with `Name::label()`, the menu tree as a constant
+/// array of `Item`, `SETTING_IDS` listing each setting's ID, and a module `key` with a
+/// type for each setting's name, which gives the type of its value when used with
+/// `Menu::get()`.
+macro_rules! settings {
+    (
+        $(#[$attr:meta])* menu $menu:ident { $($tree:tt)* }
+        names {
+            $($name:ident $(#$id:literal)? $($label:literal)? $([$($tr:literal),+ $(,)?])?,)*
+        }
+    ) => {
+        settings!(@names [] [$($name [$($label)? $($($tr),+)?],)*] $($tree)*);
+
+        $(#[$attr])*
+        pub const $menu: &[Item] = settings!(@items [] $($tree)*);
+
+        /// Stable IDs used to tag each saved setting, from the `#id` of each setting.
+        pub const SETTING_IDS: &[(Name, u8)] =
+            settings!(@ids [] [$($name $(#$id)?,)*] $($tree)*);
+
+        /// Types for each setting's name, for use with `Menu::get()`.
+        #[allow(dead_code)]
+        pub mod key {
+            use super::*;
+            settings!(@keys $($tree)*);
+        }
+    };
+
+    // Collect names and labels from the tree.
+    (@names
+        [$($key:ident [$($label:literal),+],)*] [$($name:ident [$($name_label:literal),+],)*]
+    ) => {
+        /// All names used throughout the library which must occasionally be displayed.
+        ///
+        /// The strings are effectively interned; each only appears in memory once but is
+        /// represented by an enum, allowing efficient copying, comparisons, and storage.
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        #[repr(u8)]
+        pub enum Name {
+            $($key,)*
+            $($name,)*
+        }
+
+        impl Name {
+            /// Get the label for this name in `language`.
+            pub fn label(&self, language: Language) -> &'static str {
+                match self {
+                    $(Name::$key => settings!(@label language $($label),+),)*
+                    $(Name::$name => settings!(@label language $($name_label),+),)*
+                }
+            }
+        }
+    };
+    (@names [$($acc:tt)*] $names:tt
+        $key:ident $($label:literal)? $([$($tr:literal),+ $(,)?])? { $($inner:tt)* } $($rest:tt)*
+    ) => {
+        settings!(@names
+            [$($acc)* $($key [$label],)? $($key [$($tr),+],)?] $names $($inner)* $($rest)*
+        );
+    };
+    (@names [$($acc:tt)*] $names:tt
+        $key:ident $(#$id:literal)? $($label:literal)? $([$($tr:literal),+ $(,)?])?:
+        $kind:ident($($args:tt)*) $(if $rule:tt)?; $($rest:tt)*
+    ) => {
+        settings!(@names [$($acc)* $($key [$label],)? $($key [$($tr),+],)?] $names $($rest)*);
+    };
+
+    // Build each level of the tree as an array of items.
+    (@items [$($acc:expr,)*]) => {
+        &[$($acc),*]
+    };
+    (@items [$($acc:expr,)*]
+        $key:ident $($label:literal)? $([$($tr:tt)*])? { $($inner:tt)* } $($rest:tt)*
+    ) => {
+        settings!(@items
+            [$($acc,)* Item::Category(Name::$key, settings!(@items [] $($inner)*)),] $($rest)*
+        )
+    };
+    (@items [$($acc:expr,)*]
+        $key:ident $(#$id:literal)? $($label:literal)? $([$($tr:tt)*])?:
+        $kind:ident($($args:tt)*) $(if $rule:tt)?; $($rest:tt)*
+    ) => {
+        settings!(@items [$($acc,)*
+            Item::Setting(
+                settings!(@new $kind Name::$key, $($args)*)$(.when(settings!(@rule $rule)))?
+            ),
+        ] $($rest)*)
+    };
+
+    // List the ID of each saved setting, followed by the IDs of other names.
+    (@ids [$($acc:expr,)*] [$($name:ident $(#$id:literal)?,)*]) => {
+        &[$($acc,)* $($((Name::$name, $id),)?)*]
+    };
+    (@ids [$($acc:expr,)*] $names:tt
+        $key:ident $($label:literal)? $([$($tr:tt)*])? { $($inner:tt)* } $($rest:tt)*
+    ) => {
+        settings!(@ids [$($acc,)*] $names $($inner)* $($rest)*)
+    };
+    (@ids [$($acc:expr,)*] $names:tt
+        $key:ident $(#$id:literal)? $($label:literal)? $([$($tr:tt)*])?:
+        $kind:ident($($args:tt)*) $(if $rule:tt)?; $($rest:tt)*
+    ) => {
+        settings!(@ids [$($acc,)* $((Name::$key, $id),)?] $names $($rest)*)
+    };
+
+    // Declare a type for each setting which has a value.
+    (@keys) => {};
+    (@keys $key:ident $($label:literal)? $([$($tr:tt)*])? { $($inner:tt)* } $($rest:tt)*) => {
+        settings!(@keys $($inner)* $($rest)*);
+    };
+    (@keys
+        $key:ident $(#$id:literal)? $($label:literal)? $([$($tr:tt)*])?:
+        Action($($args:tt)*) $(if $rule:tt)?; $($rest:tt)*
+    ) => {
+        settings!(@keys $($rest)*);
+    };
+    (@keys
+        $key:ident $(#$id:literal)? $($label:literal)? $([$($tr:tt)*])?:
+        $kind:ident($($args:tt)*) $(if $rule:tt)?; $($rest:tt)*
+    ) => {
+        #[derive(Copy, Clone, Debug)]
+        pub struct $key;
+
+        impl SettingKey for $key {
+            type Value = settings!(@type $kind);
+            const NAME: Name = Name::$key;
+        }
+
+        settings!(@keys $($rest)*);
+    };
+
+    (@new OnOff $name:expr, $($args:tt)*) => { Setting::new_onoff($name, true, $($args)*) };
+    (@new Numeric $name:expr, $($args:tt)*) => { Setting::new_numeric($name, true, $($args)*) };
+    (@new Choice $name:expr, $($args:tt)*) => { Setting::new_choice($name, true, $($args)*) };
+    (@new Offset $name:expr, $($args:tt)*) => { Setting::new_offset($name, true, $($args)*) };
+    (@new Time $name:expr, $($args:tt)*) => { Setting::new_time($name, true, $($args)*) };
+    (@new Date $name:expr, $($args:tt)*) => { Setting::new_date($name, true, $($args)*) };
+    (@new Color $name:expr, $($args:tt)*) => { Setting::new_color($name, true, $($args)*) };
+    (@new Action $name:expr, $($args:tt)*) => { Setting::new_action($name, true, $($args)*) };
+
+    (@type OnOff) => { bool };
+    (@type Numeric) => { i16 };
+    (@type Choice) => { Name };
+    (@type Offset) => { i16 };
+    (@type Time) => { (u8, u8) };
+    (@type Date) => { (u16, u8, u8) };
+    (@type Color) => { (u8, u8, u8) };
+
+    (@label $language:ident $label:literal) => { $label };
+    (@label $language:ident $en:literal, $fr:literal, $es:literal, $it:literal, $de:literal) => {
+        match $language {
+            Language::English => $en,
+            Language::French => $fr,
+            Language::Spanish => $es,
+            Language::Italian => $it,
+            Language::German => $de,
+        }
+    };
+
+    (@rule (!$name:ident)) => { Rule::Off(Name::$name) };
+    (@rule ($name:ident == $choice:ident)) => { Rule::Is(Name::$name, Name::$choice) };
+    (@rule $name:ident) => { Rule::On(Name::$name) };
+}
@@ -0,0 +1,346 @@
+use crate::menu::{Item, Rule, Setting, SettingKey};
+use crate::map::{MAP_NAMES, ROUTE_END_NAMES};
+use crate::tz::ZONE_NAMES;
+use crate::alarm::DAY_NAMES;
+use crate::lang::{Language, LANGUAGE_NAMES};
+use crate::format::{DATE_FORMAT_NAMES, TIME_FORMAT_NAMES};
+
+settings! {
+    /// Menu tree used by Clock.
+    menu MENU {
+        DateTime ["Date/Time", "Date/Heure", "Fecha/Hora", "Data/Ora", "Datum/Zeit"] {
+            // The date and time are in UTC, and are restored from the RTC so are not saved.
+            GPSTime #1 ["GPS time", "Heure GPS", "Hora GPS", "Ora GPS", "GPS-Zeit"]: OnOff(true);
+            Date [
+                "Date (UTC)", "Date (UTC)", "Fecha (UTC)", "Data (UTC)", "Datum (UTC)",
+            ]: Date(2000, 1, 1) if (!GPSTime);
+            Time [
+                "Time (UTC)", "Heure (UTC)", "Hora (UTC)", "Ora (UTC)", "Uhrzeit (UTC)",
+            ]: Time(0, 0) if (!GPSTime);
+            TimeZone #2 [
+                "Time zone", "Fuseau", "Zona horaria", "Fuso orario", "Zeitzone",
+            ]: Choice(2, &ZONE_NAMES);
+            UTCOffset #3 [
+                "UTC offset", "Décalage UTC", "Desfase UTC", "Scarto UTC", "UTC-Versatz",
+            ]: Offset(0) if (TimeZone == Manual);
+        }
+        Map ["Map", "Carte", "Mapa", "Mappa", "Karte"] {
+            Route #4 ["Route", "Parcours", "Ruta", "Percorso", "Route"]: Choice(0, MAP_NAMES);
+            DayOffset #5 [
+                "Day offset", "Décalage jour", "Desfase días", "Scarto giorni", "Tagversatz",
+            ]: Numeric(0, 999, 0);
+            AnimateRoute #6 [
+                "Animate route", "Animer route", "Animar ruta", "Animazione", "Animation",
+            ]: OnOff(true) if (!LiveWalker);
+            LiveWalker #7 [
+                "Live walker", "Marcheur", "Caminante", "Camminatore", "Wanderer",
+            ]: OnOff(false);
+            WalkStartHour #8 [
+                "Walk start", "Début marche", "Inicio marcha", "Ora partenza", "Wanderstart",
+            ]: Numeric(0, 23, 7) if LiveWalker;
+            WalkEndHour #9 [
+                "Walk end", "Fin marche", "Fin marcha", "Ora arrivo", "Wanderende",
+            ]: Numeric(0, 23, 17) if LiveWalker;
+            HourlyImages #10 [
+                "Hourly images", "Images/heure", "Imágenes/hora", "Immagini/ora", "Stundenbilder",
+            ]: OnOff(true);
+            RolloverHour #11 [
+                "Rollover hour", "Heure bascule", "Hora cambio", "Ora cambio", "Wechselstunde",
+            ]: Numeric(0, 23, 0);
+            RouteEnd #12 [
+                "Route end", "Fin parcours", "Fin de ruta", "Fine percorso", "Routenende",
+            ]: Choice(0, ROUTE_END_NAMES);
+            ResetRoute [
+                "Reset route", "Réinit. route", "Reinicio ruta", "Ricomincia", "Neu starten",
+            ]: Action(true);
+        }
+        Display ["Display", "Affichage", "Pantalla", "Schermo", "Anzeige"] {
+            Brightness #13 [
+                "Brightness", "Luminosité", "Brillo", "Luminosità", "Helligkeit",
+            ]: Numeric(0, 10, 10);
+            TextColor #58 [
+                "Text colour", "Couleur texte", "Color texto", "Colore testo", "Textfarbe",
+            ]: Color(15, 15, 15);
+            TimeFormat #61 [
+                "Time format", "Format heure", "Formato hora", "Formato ora", "Zeitformat",
+            ]: Choice(0, TIME_FORMAT_NAMES);
+            DateFormat #62 [
+                "Date format", "Format date", "Formato fecha", "Formato data", "Datumsformat",
+            ]: Choice(0, DATE_FORMAT_NAMES);
+            ShowWeekday #63 [
+                "Show weekday", "Jour semaine", "Día semana", "Giorno sett.", "Wochentag",
+            ]: OnOff(false);
+            Night ["Night", "Nuit", "Noche", "Notte", "Nacht"] {
+                DimAtNight #14 [
+                    "Dim at night", "Atténuer nuit", "Atenuar noche",
+                    "Attenua notte", "Nachts dimmen",
+                ]: OnOff(true);
+                DimBrightness #15 [
+                    "Dim brightness", "Lum. de nuit", "Brillo noche",
+                    "Lumin. notte", "Nachthellig.",
+                ]: Numeric(0, 10, 8) if DimAtNight;
+                DimStart #53 [
+                    "Dim start", "Début nuit", "Inicio noche", "Inizio notte", "Nachtbeginn",
+                ]: Time(23, 0) if DimAtNight;
+                DimEnd #54 [
+                    "Dim end", "Fin nuit", "Fin noche", "Fine notte", "Nachtende",
+                ]: Time(7, 0) if DimAtNight;
+            }
+        }
+        Faces ["Faces", "Cadrans", "Esferas", "Quadranti", "Zifferblätter"] {
+            // One setting for each face in `face::FACES`, enabling it in the DISPLAY key
+            // cycle, followed by face-specific settings.
+            RouteFace #18 [
+                "Route map", "Carte route", "Mapa ruta", "Mappa tappe", "Routenkarte",
+            ]: OnOff(true);
+            PhotoFace #19 ["Photo", "Photo", "Foto", "Foto", "Foto"]: OnOff(true);
+            BigClockFace #20 [
+                "Big clock", "Grande heure", "Reloj grande", "Ora grande", "Große Uhr",
+            ]: OnOff(false);
+            QrFace #21 ["QR code", "Code QR", "Código QR", "Codice QR", "QR-Code"]: OnOff(false);
+            AnalogFace #22 [
+                "Analog", "Analogique", "Analógico", "Analogico", "Analog",
+            ]: OnOff(false);
+            WordFace #23 [
+                "Word clock", "Horloge mots", "Reloj palabra", "Orol. parole", "Wortuhr",
+            ]: OnOff(false);
+            TimerFace #24 ["Timer", "Minuteur", "Temporizador", "Timer", "Timer"]: OnOff(true);
+            StopwatchFace #25 [
+                "Stopwatch", "Chrono", "Cronómetro", "Cronometro", "Stoppuhr",
+            ]: OnOff(true);
+            AnalogSeconds #26 [
+                "Second hand", "Trotteuse", "Segundero", "Lancetta sec.", "Sekundenzeig.",
+            ]: OnOff(true);
+            AnalogMap #27 [
+                "Analog on map", "Analog. carte", "Analóg. mapa", "Analog. mappa", "Analog+Karte",
+            ]: OnOff(false);
+            WordExact #28 [
+                "Exact minutes", "Min. exactes", "Min. exactos", "Minuti esatti", "Genaue Min.",
+            ]: OnOff(false);
+            TimerMinutes #29 [
+                "Timer (min)", "Minuteur(min)", "Tempor. (min)", "Timer (min)", "Timer (Min)",
+            ]: Numeric(1, 99, 5);
+        }
+        Alarms ["Alarms", "Alarmes", "Alarmas", "Sveglie", "Wecker"] {
+            Alarm1 ["Alarm 1", "Alarme 1", "Alarma 1", "Sveglia 1", "Wecker 1"] {
+                Alarm1 #30: OnOff(false);
+                Alarm1Time #55 [
+                    "Alarm 1 time", "Heure al. 1", "Hora alarma 1", "Ora sveglia 1", "Weckzeit 1",
+                ]: Time(7, 0) if Alarm1;
+                Alarm1Days #33 [
+                    "Alarm 1 days", "Jours al. 1", "Días alarma 1", "Giorni sv. 1", "Wecktage 1",
+                ]: Choice(1, DAY_NAMES) if Alarm1;
+            }
+            Alarm2 ["Alarm 2", "Alarme 2", "Alarma 2", "Sveglia 2", "Wecker 2"] {
+                Alarm2 #34: OnOff(false);
+                Alarm2Time #56 [
+                    "Alarm 2 time", "Heure al. 2", "Hora alarma 2", "Ora sveglia 2", "Weckzeit 2",
+                ]: Time(8, 0) if Alarm2;
+                Alarm2Days #37 [
+                    "Alarm 2 days", "Jours al. 2", "Días alarma 2", "Giorni sv. 2", "Wecktage 2",
+                ]: Choice(2, DAY_NAMES) if Alarm2;
+            }
+            Alarm3 ["Alarm 3", "Alarme 3", "Alarma 3", "Sveglia 3", "Wecker 3"] {
+                Alarm3 #38: OnOff(false);
+                Alarm3Time #57 [
+                    "Alarm 3 time", "Heure al. 3", "Hora alarma 3", "Ora sveglia 3", "Weckzeit 3",
+                ]: Time(7, 0) if Alarm3;
+                Alarm3Days #41 [
+                    "Alarm 3 days", "Jours al. 3", "Días alarma 3", "Giorni sv. 3", "Wecktage 3",
+                ]: Choice(0, DAY_NAMES) if Alarm3;
+            }
+            SnoozeMinutes #42 [
+                "Snooze (min)", "Répét. (min)", "Posponer(min)", "Rinvio (min)", "Snooze (Min)",
+            ]: Numeric(1, 30, 9);
+        }
+        Greetings ["Greetings", "Messages", "Saludos", "Auguri", "Grüße"] {
+            Holidays #43 ["Holidays", "Fêtes", "Festivos", "Festività", "Feiertage"]: OnOff(true);
+            Birthday1 ["Birthday 1", "Anniv. 1", "Cumpleaños 1", "Compleanno 1", "Geburtstag 1"] {
+                Birthday1 #44: OnOff(false);
+                Birthday1Month #45 [
+                    "Bday 1 month", "Mois anniv. 1", "Mes cumple 1", "Mese compl. 1", "Geb.monat 1",
+                ]: Numeric(1, 12, 1) if Birthday1;
+                Birthday1Day #46 [
+                    "Bday 1 day", "Jour anniv. 1", "Día cumple 1", "Giorno comp.1", "Geb.tag 1",
+                ]: Numeric(1, 31, 1) if Birthday1;
+            }
+            Birthday2 ["Birthday 2", "Anniv. 2", "Cumpleaños 2", "Compleanno 2", "Geburtstag 2"] {
+                Birthday2 #47: OnOff(false);
+                Birthday2Month #48 [
+                    "Bday 2 month", "Mois anniv. 2", "Mes cumple 2", "Mese compl. 2", "Geb.monat 2",
+                ]: Numeric(1, 12, 1) if Birthday2;
+                Birthday2Day #49 [
+                    "Bday 2 day", "Jour anniv. 2", "Día cumple 2", "Giorno comp.2", "Geb.tag 2",
+                ]: Numeric(1, 31, 1) if Birthday2;
+            }
+            Anniversary ["Anniversary", "Mariage", "Aniversario", "Anniversario", "Jahrestag"] {
+                Anniversary #50: OnOff(false);
+                AnnivMonth #51 [
+                    "Anniv month", "Mois mariage", "Mes aniv.", "Mese anniv.", "Jahrest.monat",
+                ]: Numeric(1, 12, 1) if Anniversary;
+                AnnivDay #52 [
+                    "Anniv day", "Jour mariage", "Día aniv.", "Giorno anniv.", "Jahrest.tag",
+                ]: Numeric(1, 31, 1) if Anniversary;
+            }
+        }
+        System ["System", "Système", "Sistema", "Sistema", "System"] {
+            Language #60 [
+                "Language", "Langue", "Idioma", "Lingua", "Sprache",
+            ]: Choice(0, LANGUAGE_NAMES);
+            MenuTimeout #59 [
+                "Menu timeout", "Délai menu", "Espera menú", "Timeout menu", "Menü-Timeout",
+            ]: Numeric(0, 300, 60);
+            RestartGPS [
+                "Restart GPS", "Relancer GPS", "Reiniciar GPS", "Riavvia GPS", "GPS-Neustart",
+            ]: Action(false);
+            TestPattern [
+                "Test pattern", "Mire", "Patrón prueba", "Test schermo", "Testbild",
+            ]: Action(false);
+            FactoryReset [
+                "Factory reset", "Réinit. usine", "Restablecer", "Ripristino", "Werksreset",
+            ]: Action(true);
+        }
+    }
+
+    names {
+        Unused         "",
+        // Text shown other than menu items.
+        On             ["On", "Oui", "Sí", "Sì", "Ein"],
+        Off            ["Off", "Non", "No", "No", "Aus"],
+        PressEnter [
+            "Press ENTER", "Appuyer ENTER", "Pulsar ENTER", "Premi ENTER", "ENTER drücken",
+        ],
+        Sure [
+            "Sure? ENTER", "Sûr? ENTER", "¿Seguro? ENTER", "Sicuro? ENTER", "Sicher? ENTER",
+        ],
+        Menu           ["MENU", "MENU", "MENÚ", "MENU", "MENÜ"],
+        EnterForMenu [
+            "ENTER for menu", "ENTER : menu", "ENTER: menú", "ENTER: menu", "ENTER: Menü",
+        ],
+        OneDay         ["day", "jour", "día", "giorno", "Tag"],
+        Days           ["days", "jours", "días", "giorni", "Tage"],
+        // Short month names.
+        Jan            ["JAN", "JAN", "ENE", "GEN", "JAN"],
+        Feb            ["FEB", "FÉV", "FEB", "FEB", "FEB"],
+        Mar            ["MAR", "MAR", "MAR", "MAR", "MÄR"],
+        Apr            ["APR", "AVR", "ABR", "APR", "APR"],
+        May            ["MAY", "MAI", "MAY", "MAG", "MAI"],
+        Jun            ["JUN", "JUN", "JUN", "GIU", "JUN"],
+        Jul            ["JUL", "JUL", "JUL", "LUG", "JUL"],
+        Aug            ["AUG", "AOÛ", "AGO", "AGO", "AUG"],
+        Sep            ["SEP", "SEP", "SEP", "SET", "SEP"],
+        Oct            ["OCT", "OCT", "OCT", "OTT", "OKT"],
+        Nov            ["NOV", "NOV", "NOV", "NOV", "NOV"],
+        Dec            ["DEC", "DÉC", "DIC", "DIC", "DEZ"],
+        // Short weekday names.
+        Mon            ["MON", "LUN", "LUN", "LUN", "MO"],
+        Tue            ["TUE", "MAR", "MAR", "MAR", "DI"],
+        Wed            ["WED", "MER", "MIÉ", "MER", "MI"],
+        Thu            ["THU", "JEU", "JUE", "GIO", "DO"],
+        Fri            ["FRI", "VEN", "VIE", "VEN", "FR"],
+        Sat            ["SAT", "SAM", "SÁB", "SAB", "SA"],
+        Sun            ["SUN", "DIM", "DOM", "DOM", "SO"],
+        // Labels on the timer and stopwatch faces, of up to 10 characters, or 4 for Lap.
+        TimerTitle     ["TIMER", "MINUTEUR", "TEMPORIZ.", "TIMER", "TIMER"],
+        TimerDone      ["DONE", "TERMINÉ", "TERMINADO", "FINITO", "FERTIG"],
+        TimerReady     ["READY", "PRÊT", "LISTO", "PRONTO", "BEREIT"],
+        TimerPaused    ["PAUSED", "PAUSE", "PAUSA", "PAUSA", "PAUSE"],
+        StopwatchTitle ["STOPWATCH", "CHRONO", "CRONÓMETRO", "CRONOMETRO", "STOPPUHR"],
+        Lap            ["LAP", "TOUR", "VTA", "GIRO", "RND"],
+        // Choices for date and time formats.
+        Hour24         ["24-hour", "24 heures", "24 horas", "24 ore", "24 Stunden"],
+        Hour12 [
+            "12-hour AM/PM", "12 h AM/PM", "12 h AM/PM", "12 ore AM/PM", "12 Std. AM/PM",
+        ],
+        DayMonth       ["Day/month", "Jour/mois", "Día/mes", "Giorno/mese", "Tag/Monat"],
+        MonthDay       ["Month/day", "Mois/jour", "Mes/día", "Mese/giorno", "Monat/Tag"],
+        IsoDate        "ISO Y-M-D",
+        // Languages, each named in itself.
+        English        "English",
+        French         "Français",
+        Spanish        "Español",
+        Italian        "Italiano",
+        German         "Deutsch",
+        // Choices for when the end of the route is reached.
+        Restart        ["Start again", "Recommencer", "Reiniciar", "Ricomincia", "Neu beginnen"],
+        StayAtEnd [
+            "Stay at end", "Rester au bout", "Parar al final", "Resta in fondo", "Am Ende",
+        ],
+        // Choices of days for alarms.
+        EveryDay [
+            "Every day", "Tous les jours", "Todos los días", "Ogni giorno", "Jeden Tag",
+        ],
+        Weekdays       ["Weekdays", "En semaine", "Laborables", "Feriali", "Werktags"],
+        Weekends       ["Weekends", "Week-ends", "Fines semana", "Weekend", "Wochenende"],
+        Monday         ["Monday", "Lundi", "Lunes", "Lunedì", "Montag"],
+        Tuesday        ["Tuesday", "Mardi", "Martes", "Martedì", "Dienstag"],
+        Wednesday      ["Wednesday", "Mercredi", "Miércoles", "Mercoledì", "Mittwoch"],
+        Thursday       ["Thursday", "Jeudi", "Jueves", "Giovedì", "Donnerstag"],
+        Friday         ["Friday", "Vendredi", "Viernes", "Venerdì", "Freitag"],
+        Saturday       ["Saturday", "Samedi", "Sábado", "Sabato", "Samstag"],
+        Sunday         ["Sunday", "Dimanche", "Domingo", "Domenica", "Sonntag"],
+        // Routes.
+        CaminoFrances  "Camino Frances",
+        HolyIsland     "Holy Island",
+        Scotland       ["Scotland", "Écosse", "Escocia", "Scozia", "Schottland"],
+        Shikoku        "Shikoku",
+        ViaFrancigena  "Via Francigena",
+        ViaPodiensis   "Via Podiensis",
+        NoMap          ["None", "Aucune", "Ninguna", "Nessuna", "Keine"],
+        // Time zones.
+        Manual [
+            "Fixed offset", "Décalage fixe", "Desfase fijo", "Scarto fisso", "Fester Versatz",
+        ],
+        UTC            "UTC",
+        London         ["London", "Londres", "Londres", "Londra", "London"],
+        Dublin         ["Dublin", "Dublin", "Dublín", "Dublino", "Dublin"],
+        Lisbon         ["Lisbon", "Lisbonne", "Lisboa", "Lisbona", "Lissabon"],
+        CentralEurope [
+            "Central Europe", "Europe centr.", "Europa central", "Europa centr.", "Mitteleuropa",
+        ],
+        EasternEurope [
+            "Eastern Europe", "Europe est", "Europa Este", "Europa orient.", "Osteuropa",
+        ],
+        Moscow         ["Moscow", "Moscou", "Moscú", "Mosca", "Moskau"],
+        Dubai          ["Dubai", "Dubaï", "Dubái", "Dubai", "Dubai"],
+        India          ["India", "Inde", "India", "India", "Indien"],
+        Nepal          ["Nepal", "Népal", "Nepal", "Nepal", "Nepal"],
+        China          ["China", "Chine", "China", "Cina", "China"],
+        Japan          ["Japan", "Japon", "Japón", "Giappone", "Japan"],
+        Adelaide       "Adelaide",
+        Brisbane       "Brisbane",
+        Sydney         "Sydney",
+        Auckland       "Auckland",
+        Tonga          "Tonga",
+        Kiribati       "Kiribati",
+        Honolulu       "Honolulu",
+        Anchorage      "Anchorage",
+        LosAngeles     "Los Angeles",
+        Denver         "Denver",
+        Phoenix        "Phoenix",
+        Chicago        "Chicago",
+        NewYork        ["New York", "New York", "Nueva York", "New York", "New York"],
+        Halifax        "Halifax",
+        Newfoundland   ["Newfoundland", "Terre-Neuve", "Terranova", "Terranova", "Neufundland"],
+        SaoPaulo       "Sao Paulo",
+        Santiago       "Santiago",
+    }
+}
+
+impl From<&Name> for &'static str {
+    fn from(name: &Name) -> &'static str {
+        name.label(Language::English)
+    }
+}
+
+impl core::fmt::Display for Name {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.into())
+    }
+}
+
+impl Name {
+    pub fn into_str(&self) -> &'static str {
+        self.into()
+    }
+}
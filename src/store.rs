@@ -0,0 +1,281 @@
+use crate::{Name, menu::Menu, settings::SETTING_IDS, tz::ZONE_NAMES};
+
+/// Flag set in the ID of a record whose value is in the following word.
+const WIDE: u8 = 0x80;
+
+/// Serialise settings from `menu` which differ from `defaults` to `data`,
+/// returning the number of words used.
+///
+/// Each setting is stored as a record with its ID in the low byte of the first word.
+/// Values which fit in a byte are stored in the high byte, and otherwise the ID has
+/// the `WIDE` flag set and the value is stored in the following word. Unused words
+/// are zeroed. Any settings which do not fit in `data` are left out and so will take
+/// their defaults.
+pub fn serialise<const N: usize>(
+    menu: &Menu<N>, defaults: &Menu<N>, data: &mut [u16]
+) -> usize {
+    let mut n = 0;
+    for &(name, id) in SETTING_IDS.iter() {
+        let (setting, default) = match (menu.setting(name), defaults.setting(name)) {
+            (Some(setting), Some(default)) => (setting, default),
+            _ => continue,
+        };
+        if setting.serialise() == default.serialise() {
+            continue;
+        }
+        let (record, len) = match setting.serialise_byte() {
+            Some(byte) => ([id as u16 | (byte as u16) << 8, 0], 1),
+            None => ([(id | WIDE) as u16, setting.serialise()], 2),
+        };
+        if let Some(words) = data.get_mut(n..n + len) {
+            words.copy_from_slice(&record[..len]);
+            n += len;
+        }
+    }
+    data[n..].iter_mut().for_each(|w| *w = 0);
+    n
+}
+
+/// Deserialise settings in `menu` from records in `data`, returning the number
+/// of records with invalid values.
+///
+/// Records with unknown IDs are ignored, and settings without a record or with
+/// an invalid value are not changed.
+pub fn deserialise<const N: usize>(menu: &mut Menu<N>, data: &[u16]) -> usize {
+    let mut invalid = 0;
+    let mut words = data.iter().copied();
+    while let Some(word) = words.next() {
+        if word == 0 {
+            break;
+        }
+        let id = word as u8;
+        let wide = id & WIDE != 0;
+        let value = if wide { words.next().unwrap_or(0) } else { word >> 8 };
+        let name = match SETTING_IDS.iter().find(|(_, i)| *i == id & !WIDE) {
+            Some(&(name, _)) => name,
+            None => continue,
+        };
+        let valid = match menu.setting_mut(name) {
+            Some(setting) if wide => setting.deserialise(value),
+            Some(setting) => setting.deserialise_byte(value as u8),
+            None => continue,
+        };
+        if valid.is_none() {
+            invalid += 1;
+        }
+    }
+    invalid
+}
+
+/// Restore settings in `menu` from the 18 words saved by version 2, the positional format
+/// used before settings were tagged, returning the number of invalid values.
+///
+/// Version 2 stored one word per setting of the original menu, with an "Automatic DST"
+/// switch for UK time and a whole hour UTC offset in place of the time zone, and whole
+/// hours for the dimming times. The date and time are not restored, since they come
+/// from the RTC.
+pub fn migrate_v2<const N: usize>(menu: &mut Menu<N>, data: &[u16; 18]) -> usize {
+    let zone = if data[7] != 0 { Name::London } else { Name::Manual };
+    let zone = ZONE_NAMES.iter().position(|z| *z == zone).unwrap() as u16;
+    let hours = |word: u16| word.saturating_mul(60);
+    let offset = (data[8] as i16).saturating_mul(60) as u16;
+    let settings = [
+        (Name::GPSTime, data[0]),
+        (Name::TimeZone, zone),
+        (Name::UTCOffset, offset),
+        (Name::Route, data[9]),
+        (Name::DayOffset, data[10]),
+        (Name::AnimateRoute, data[11]),
+        (Name::HourlyImages, data[12]),
+        (Name::Brightness, data[13]),
+        (Name::DimAtNight, data[14]),
+        (Name::DimBrightness, data[15]),
+        (Name::DimStart, hours(data[16])),
+        (Name::DimEnd, hours(data[17])),
+    ];
+    settings.iter()
+        .filter(|&&(name, word)| {
+            menu.setting_mut(name).and_then(|setting| setting.deserialise(word)).is_none()
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::{Date, Month};
+    use crate::{clock::ClockMenu, menu::{Setting, Value}, settings::MENU};
+
+    /// Check that `setting` has a value it could have been given from the menu.
+    fn in_range(setting: &Setting) -> bool {
+        match *setting.value() {
+            Value::OnOff(_) | Value::Action { .. } => true,
+            Value::Numeric { min, max, val } => (min..=max).contains(&val),
+            Value::Choice { index, choices } => index < choices.len(),
+            Value::Offset(m) => (-12 * 60..=14 * 60).contains(&m) && m % 15 == 0,
+            Value::Time { hour, minute } => hour < 24 && minute < 60,
+            Value::Date { year, month, day } => {
+                (2000..=2099).contains(&year) && Month::try_from(month).ok()
+                    .and_then(|month| Date::from_calendar_date(year as i32, month, day).ok())
+                    .is_some()
+            },
+            Value::Color { r, g, b } => r < 16 && g < 16 && b < 16,
+        }
+    }
+
+    /// Pseudo-random words from a xorshift generator, so failures can be reproduced.
+    struct Words(u32);
+
+    impl Iterator for Words {
+        type Item = u16;
+        fn next(&mut self) -> Option<u16> {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            Some((self.0 >> 8) as u16)
+        }
+    }
+
+    #[test]
+    fn setting_ids_are_unique() {
+        for (i, &(name, id)) in SETTING_IDS.iter().enumerate() {
+            assert!(id != 0 && id & WIDE == 0, "{:?} has invalid ID {}", name, id);
+            for &(other, other_id) in SETTING_IDS[i + 1..].iter() {
+                assert!(id != other_id, "{:?} and {:?} share ID {}", name, other, id);
+                assert!(name != other, "{:?} has two IDs", name);
+            }
+        }
+    }
+
+    #[test]
+    fn deserialise_random_data() {
+        let mut words = Words(0x1234_5678);
+        let mut rejected = 0;
+        for _ in 0..2000 {
+            let mut data = [0u16; 32];
+            for word in data.iter_mut() {
+                // Mostly use known IDs, so that most records reach a setting.
+                let random = words.next().unwrap();
+                *word = match random % 4 {
+                    0 => random,
+                    _ => {
+                        let (_, id) = SETTING_IDS[random as usize % SETTING_IDS.len()];
+                        let wide = if random & 0x80 != 0 { WIDE } else { 0 };
+                        (words.next().unwrap() & 0xff00) | (id | wide) as u16
+                    },
+                };
+            }
+
+            let mut menu = ClockMenu::new(MENU);
+            let invalid = deserialise(&mut menu, &data);
+            assert!(invalid <= data.len());
+            rejected += invalid;
+            for &(name, _) in SETTING_IDS.iter() {
+                if let Some(setting) = menu.setting(name) {
+                    assert!(in_range(setting), "{:?} out of range from {:04x?}", setting, data);
+                }
+            }
+        }
+        assert!(rejected > 0);
+    }
+
+    /// Check every saved setting in `menu` has the same value as in `other`.
+    fn assert_same<const N: usize>(menu: &Menu<N>, other: &Menu<N>) {
+        for &(name, _) in SETTING_IDS.iter() {
+            if let Some(setting) = menu.setting(name) {
+                let other = other.setting(name).unwrap();
+                assert_eq!(setting.serialise(), other.serialise(), "{:?} differs", name);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_byte_and_wide_values() {
+        let defaults = ClockMenu::new(MENU);
+        let mut menu = ClockMenu::new(MENU);
+        menu.setting_set_numeric(Name::Brightness, 3).unwrap();
+        menu.setting_set_numeric(Name::DayOffset, 900).unwrap();
+        menu.setting_set_choice(Name::TimeZone, Name::Manual).unwrap();
+        menu.setting_mut(Name::UTCOffset).unwrap().deserialise(-150i16 as u16).unwrap();
+        menu.setting_set_time(Name::DimStart, 21, 45).unwrap();
+        menu.setting_set_onoff(Name::DimAtNight, false).unwrap();
+
+        let mut data = [0xffffu16; 58];
+        let n = serialise(&menu, &defaults, &mut data);
+        assert!(n > 6 && data[n..].iter().all(|&w| w == 0));
+        assert!(data[..n].iter().any(|&w| w as u8 & WIDE != 0), "no wide records");
+
+        let mut restored = ClockMenu::new(MENU);
+        assert_eq!(deserialise(&mut restored, &data), 0);
+        assert_same(&menu, &restored);
+        assert_eq!(restored.setting_numeric(Name::DayOffset), Some(900));
+        assert_eq!(restored.setting_time(Name::DimStart), Some((21, 45)));
+        assert_eq!(restored.setting(Name::UTCOffset).unwrap().offset(), Some(-150));
+    }
+
+    #[test]
+    fn unknown_ids_are_skipped() {
+        let (_, brightness) = SETTING_IDS.iter().find(|(n, _)| *n == Name::Brightness).unwrap();
+        let (_, dim_end) = SETTING_IDS.iter().find(|(n, _)| *n == Name::DimEnd).unwrap();
+        let data = [
+            0x7f | 0x1200,
+            (0x7f | WIDE) as u16, 0x1234,
+            *brightness as u16 | 0x0400,
+            0x7e | 0x3400,
+            (*dim_end | WIDE) as u16, 6 * 60 + 30,
+        ];
+        let mut menu = ClockMenu::new(MENU);
+        assert_eq!(deserialise(&mut menu, &data), 0);
+        assert_eq!(menu.setting_numeric(Name::Brightness), Some(4));
+        assert_eq!(menu.setting_time(Name::DimEnd), Some((6, 30)));
+    }
+
+    #[test]
+    fn missing_records_leave_defaults() {
+        let defaults = ClockMenu::new(MENU);
+        let mut menu = ClockMenu::new(MENU);
+        assert_eq!(deserialise(&mut menu, &[0; 58]), 0);
+        assert_same(&menu, &defaults);
+
+        let (_, id) = SETTING_IDS.iter().find(|(n, _)| *n == Name::Route).unwrap();
+        let mut data = [0; 58];
+        data[0] = *id as u16 | 0x0100;
+        assert_eq!(deserialise(&mut menu, &data), 0);
+        assert_eq!(menu.setting_choice(Name::Route), Some(Name::Shikoku));
+        menu.setting_set_choice(Name::Route, Name::NoMap).unwrap();
+        assert_same(&menu, &defaults);
+    }
+
+    #[test]
+    fn overflow_drops_settings_cleanly() {
+        // Change every setting, which needs more records than fit in the block.
+        let defaults = ClockMenu::new(MENU);
+        let mut menu = ClockMenu::new(MENU);
+        for &(name, _) in SETTING_IDS.iter() {
+            if let Some(setting) = menu.setting_mut(name) {
+                setting.inc(0);
+            }
+        }
+
+        let mut data = [0u16; 58];
+        let n = serialise(&menu, &defaults, &mut data);
+        assert!(n <= data.len());
+
+        let mut restored = ClockMenu::new(MENU);
+        assert_eq!(deserialise(&mut restored, &data), 0);
+        let mut dropped = 0;
+        for &(name, _) in SETTING_IDS.iter() {
+            let setting = match restored.setting(name) {
+                Some(setting) => setting.serialise(),
+                None => continue,
+            };
+            let saved = menu.setting(name).unwrap().serialise();
+            let default = defaults.setting(name).unwrap().serialise();
+            assert!(setting == saved || setting == default, "{:?} corrupted", name);
+            if setting != saved {
+                dropped += 1;
+            }
+        }
+        assert!(dropped > 0);
+    }
+}
@@ -0,0 +1,99 @@
+/// A countdown timer, counting down whole seconds from a set duration.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Countdown {
+    /// Duration the timer counts down from, in seconds.
+    pub duration: u32,
+    /// Seconds left to run.
+    pub remaining: u32,
+    /// Whether the timer is currently counting down.
+    pub running: bool,
+}
+
+impl Countdown {
+    pub const fn new(duration: u32) -> Self {
+        Countdown { duration, remaining: duration, running: false }
+    }
+
+    /// Count down `secs` seconds, returning true if the timer has just finished.
+    pub fn tick(&mut self, secs: u32) -> bool {
+        if self.running && self.remaining > 0 {
+            self.remaining = self.remaining.saturating_sub(secs);
+            if self.remaining == 0 {
+                self.running = false;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether the timer has run down to zero and not yet been reset.
+    pub fn finished(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Start or pause the timer, or reset it if it has finished.
+    pub fn start_stop(&mut self) {
+        if self.finished() {
+            self.reset();
+        } else {
+            self.running = !self.running;
+        }
+    }
+
+    /// Stop the timer and reset it to its full duration.
+    pub fn reset(&mut self) {
+        self.remaining = self.duration;
+        self.running = false;
+    }
+
+    /// Set a new duration in seconds, resetting the timer unless it is running.
+    pub fn set_duration(&mut self, duration: u32) {
+        self.duration = duration;
+        if !self.running {
+            self.reset();
+        }
+    }
+}
+
+/// A stopwatch, counting up whole seconds, with a single lap time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stopwatch {
+    /// Seconds elapsed while running.
+    pub elapsed: u32,
+    /// Elapsed time when the most recent lap was recorded.
+    pub lap: Option<u32>,
+    /// Whether the stopwatch is currently counting.
+    pub running: bool,
+}
+
+impl Stopwatch {
+    pub const fn new() -> Self {
+        Stopwatch { elapsed: 0, lap: None, running: false }
+    }
+
+    /// Count up `secs` seconds if running.
+    pub fn tick(&mut self, secs: u32) {
+        if self.running {
+            self.elapsed = self.elapsed.saturating_add(secs);
+        }
+    }
+
+    /// Start or stop the stopwatch.
+    pub fn start_stop(&mut self) {
+        self.running = !self.running;
+    }
+
+    /// Record a lap time if running.
+    pub fn lap(&mut self) {
+        if self.running {
+            self.lap = Some(self.elapsed);
+        }
+    }
+
+    /// Reset the elapsed and lap times if stopped.
+    pub fn reset(&mut self) {
+        if !self.running {
+            *self = Self::new();
+        }
+    }
+}
@@ -0,0 +1,561 @@
+use time::{Date, Duration, Month, PrimitiveDateTime, UtcOffset};
+use crate::Name;
+
+/// Named time zones which may be selected from the menu.
+///
+/// `Manual` uses the fixed UTC offset from menu settings instead of a named zone.
+/// New zones should be appended to keep saved indices valid.
+pub const ZONE_NAMES: [Name; 30] = [
+    Name::Manual,
+    Name::UTC,
+    Name::London,
+    Name::Dublin,
+    Name::Lisbon,
+    Name::CentralEurope,
+    Name::EasternEurope,
+    Name::Moscow,
+    Name::Dubai,
+    Name::India,
+    Name::Nepal,
+    Name::China,
+    Name::Japan,
+    Name::Adelaide,
+    Name::Brisbane,
+    Name::Sydney,
+    Name::Auckland,
+    Name::Tonga,
+    Name::Kiribati,
+    Name::Honolulu,
+    Name::Anchorage,
+    Name::LosAngeles,
+    Name::Denver,
+    Name::Phoenix,
+    Name::Chicago,
+    Name::NewYork,
+    Name::Halifax,
+    Name::Newfoundland,
+    Name::SaoPaulo,
+    Name::Santiago,
+];
+
+/// Get the POSIX TZ rule for a named zone from `ZONE_NAMES`.
+///
+/// Returns None for `Name::Manual` or any name which is not a zone.
+pub fn zone_rule(name: Name) -> Option<&'static str> {
+    match name {
+        Name::UTC           => Some("UTC0"),
+        Name::London        => Some("GMT0BST,M3.5.0/1,M10.5.0"),
+        Name::Dublin        => Some("GMT0IST,M3.5.0/1,M10.5.0"),
+        Name::Lisbon        => Some("WET0WEST,M3.5.0/1,M10.5.0"),
+        Name::CentralEurope => Some("CET-1CEST,M3.5.0,M10.5.0/3"),
+        Name::EasternEurope => Some("EET-2EEST,M3.5.0/3,M10.5.0/4"),
+        Name::Moscow        => Some("MSK-3"),
+        Name::Dubai         => Some("<+04>-4"),
+        Name::India         => Some("IST-5:30"),
+        Name::Nepal         => Some("<+0545>-5:45"),
+        Name::China         => Some("CST-8"),
+        Name::Japan         => Some("JST-9"),
+        Name::Adelaide      => Some("ACST-9:30ACDT,M10.1.0,M4.1.0/3"),
+        Name::Brisbane      => Some("AEST-10"),
+        Name::Sydney        => Some("AEST-10AEDT,M10.1.0,M4.1.0/3"),
+        Name::Auckland      => Some("NZST-12NZDT,M9.5.0,M4.1.0/3"),
+        Name::Tonga         => Some("<+13>-13"),
+        Name::Kiribati      => Some("<+14>-14"),
+        Name::Honolulu      => Some("HST10"),
+        Name::Anchorage     => Some("AKST9AKDT,M3.2.0,M11.1.0"),
+        Name::LosAngeles    => Some("PST8PDT,M3.2.0,M11.1.0"),
+        Name::Denver        => Some("MST7MDT,M3.2.0,M11.1.0"),
+        Name::Phoenix       => Some("MST7"),
+        Name::Chicago       => Some("CST6CDT,M3.2.0,M11.1.0"),
+        Name::NewYork       => Some("EST5EDT,M3.2.0,M11.1.0"),
+        Name::Halifax       => Some("AST4ADT,M3.2.0,M11.1.0"),
+        Name::Newfoundland  => Some("NST3:30NDT,M3.2.0,M11.1.0"),
+        Name::SaoPaulo      => Some("<-03>3"),
+        Name::Santiago      => Some("<-04>4<-03>,M9.1.6/24,M4.1.6/24"),
+        _ => None,
+    }
+}
+
+/// Errors from parsing a POSIX TZ string.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Error {
+    /// A zone abbreviation was missing or malformed.
+    Abbreviation,
+    /// A UTC offset or transition time was missing or out of range.
+    Offset,
+    /// A DST transition rule was malformed or out of range.
+    Rule,
+    /// Unexpected characters followed the end of the TZ string.
+    Trailing,
+}
+
+/// A time zone described by a POSIX TZ string, such as `CET-1CEST,M3.5.0,M10.5.0/3`.
+///
+/// The zone has a standard UTC offset and optionally a daylight saving offset,
+/// which applies between two transition rules each year. Transitions into DST
+/// are in local standard time and transitions out of DST in local DST time.
+/// The start rule may fall later in the year than the end rule, as is the case
+/// for zones in the southern hemisphere.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TimeZone {
+    /// Standard offset, in seconds east of UTC.
+    std: i32,
+    dst: Option<Dst>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Dst {
+    /// Daylight saving offset, in seconds east of UTC.
+    offset: i32,
+    start: Transition,
+    end: Transition,
+}
+
+/// A rule for the date of a DST transition and the local time it occurs at.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Transition {
+    rule: Rule,
+    /// Local time of transition in seconds after midnight, from -167 to 167 hours.
+    time: i32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Rule {
+    /// `Jn`: Julian day `n` from 1 to 365, not counting February 29th.
+    Julian1(u16),
+    /// `n`: Zero-based Julian day `n` from 0 to 365, counting February 29th.
+    Julian0(u16),
+    /// `Mm.w.d`: Day `d` (0 is Sunday) of week `w` (1 to 5, where 5 is the last)
+    /// of month `m`.
+    MonthWeekDay(u8, u8, u8),
+}
+
+impl TimeZone {
+    /// Coordinated Universal Time, with no offset and no daylight saving.
+    pub const UTC: TimeZone = TimeZone { std: 0, dst: None };
+
+    /// A zone with a fixed offset of `seconds` east of UTC and no daylight saving.
+    pub const fn fixed(seconds: i32) -> TimeZone {
+        TimeZone { std: seconds, dst: None }
+    }
+
+    /// Parse a POSIX TZ string.
+    ///
+    /// If a DST abbreviation is given without an offset, the DST offset is one hour
+    /// ahead of standard time. If no transition rules are given, the current US rules
+    /// (`M3.2.0,M11.1.0`) are used. Transition times default to 02:00.
+    pub fn parse(s: &str) -> Result<TimeZone, Error> {
+        let mut p = Parser { s: s.as_bytes(), i: 0 };
+        p.abbreviation()?;
+        let std = -p.offset()?;
+        if p.done() {
+            return Ok(TimeZone { std, dst: None });
+        }
+
+        p.abbreviation()?;
+        let offset = match p.peek() {
+            Some(b',') | None => std + 3600,
+            _ => -p.offset()?,
+        };
+        let (start, end) = if p.done() {
+            let start = Transition { rule: Rule::MonthWeekDay(3, 2, 0), time: 7200 };
+            let end = Transition { rule: Rule::MonthWeekDay(11, 1, 0), time: 7200 };
+            (start, end)
+        } else {
+            p.expect(b',', Error::Rule)?;
+            let start = p.transition()?;
+            p.expect(b',', Error::Rule)?;
+            let end = p.transition()?;
+            (start, end)
+        };
+
+        if p.done() {
+            Ok(TimeZone { std, dst: Some(Dst { offset, start, end }) })
+        } else {
+            Err(Error::Trailing)
+        }
+    }
+
+    /// Get the UTC offset in effect at the UTC time `utc`.
+    pub fn offset(&self, utc: &PrimitiveDateTime) -> UtcOffset {
+        let offset = match self.dst {
+            Some(dst) if dst.active(self.std, utc) => dst.offset,
+            _ => self.std,
+        };
+        UtcOffset::from_whole_seconds(offset).unwrap_or(UtcOffset::UTC)
+    }
+
+    /// Check whether the clocks change on the local date `date`, returning `Some(true)`
+    /// if they go forward and `Some(false)` if they go back.
+    pub fn change_on(&self, date: Date) -> Option<bool> {
+        let dst = self.dst?;
+        let start = seconds(date);
+        let on = |t: Transition| (start..start + 86400).contains(&t.at(date.year()));
+        if on(dst.start) {
+            Some(dst.offset > self.std)
+        } else if on(dst.end) {
+            Some(self.std > dst.offset)
+        } else {
+            None
+        }
+    }
+}
+
+impl Dst {
+    /// Check whether DST is in effect at `utc`, given the standard offset `std`.
+    fn active(&self, std: i32, utc: &PrimitiveDateTime) -> bool {
+        let year = utc.year();
+        let now = seconds(utc.date()) + utc.time().hour() as i64 * 3600
+            + utc.time().minute() as i64 * 60 + utc.time().second() as i64;
+        let start = self.start.at(year) - std as i64;
+        let end = self.end.at(year) - self.offset as i64;
+        if start < end {
+            now >= start && now < end
+        } else {
+            now < end || now >= start
+        }
+    }
+}
+
+impl Transition {
+    /// Local time of this transition in `year`, in seconds since the Julian day epoch.
+    fn at(&self, year: i32) -> i64 {
+        seconds(self.rule.date(year)) + self.time as i64
+    }
+}
+
+impl Rule {
+    /// Date of this rule in `year`.
+    fn date(&self, year: i32) -> Date {
+        let jan1 = Date::from_ordinal_date(year, 1).unwrap();
+        let leap = time::util::is_leap_year(year);
+        match *self {
+            Rule::Julian1(n) => {
+                let n = if leap && n >= 60 { n + 1 } else { n };
+                jan1 + Duration::days(n as i64 - 1)
+            },
+            Rule::Julian0(n) => {
+                let n = if !leap && n == 365 { 364 } else { n };
+                jan1 + Duration::days(n as i64)
+            },
+            Rule::MonthWeekDay(m, w, d) => {
+                let month = Month::try_from(m).unwrap();
+                let first = Date::from_calendar_date(year, month, 1).unwrap();
+                let first_wd = first.weekday().number_days_from_sunday();
+                let mut day = 1 + (d + 7 - first_wd) % 7 + (w - 1) * 7;
+                let days = time::util::days_in_year_month(year, month);
+                while day > days {
+                    day -= 7;
+                }
+                Date::from_calendar_date(year, month, day).unwrap()
+            },
+        }
+    }
+}
+
+/// Seconds from the Julian day epoch to the start of `date`.
+fn seconds(date: Date) -> i64 {
+    date.to_julian_day() as i64 * 86400
+}
+
+/// Cursor over the bytes of a TZ string.
+struct Parser<'a> {
+    s: &'a [u8],
+    i: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.i).copied()
+    }
+
+    fn done(&self) -> bool {
+        self.i == self.s.len()
+    }
+
+    fn expect(&mut self, c: u8, err: Error) -> Result<(), Error> {
+        if self.peek() == Some(c) {
+            self.i += 1;
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+
+    /// Skip a zone abbreviation, either three or more letters or quoted in `<>`.
+    fn abbreviation(&mut self) -> Result<(), Error> {
+        let start = self.i;
+        if self.peek() == Some(b'<') {
+            self.i += 1;
+            while self.peek().map(|c| c.is_ascii_alphanumeric() || c == b'+' || c == b'-')
+                == Some(true)
+            {
+                self.i += 1;
+            }
+            let len = self.i - start - 1;
+            self.expect(b'>', Error::Abbreviation)?;
+            if len < 3 { Err(Error::Abbreviation) } else { Ok(()) }
+        } else {
+            while self.peek().map(|c| c.is_ascii_alphabetic()) == Some(true) {
+                self.i += 1;
+            }
+            if self.i - start < 3 { Err(Error::Abbreviation) } else { Ok(()) }
+        }
+    }
+
+    /// Parse an unsigned decimal number.
+    fn number(&mut self, err: Error) -> Result<u32, Error> {
+        let start = self.i;
+        let mut n: u32 = 0;
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit()) {
+            n = n.saturating_mul(10).saturating_add((c - b'0') as u32);
+            self.i += 1;
+        }
+        if self.i == start { Err(err) } else { Ok(n) }
+    }
+
+    /// Parse `[+-]hh[:mm[:ss]]` into signed seconds, with hours up to `max_hours`.
+    fn time(&mut self, max_hours: u32) -> Result<i32, Error> {
+        let sign = match self.peek() {
+            Some(b'-') => { self.i += 1; -1 },
+            Some(b'+') => { self.i += 1; 1 },
+            _ => 1,
+        };
+        let h = self.number(Error::Offset)?;
+        let mut m = 0;
+        let mut s = 0;
+        if self.peek() == Some(b':') {
+            self.i += 1;
+            m = self.number(Error::Offset)?;
+            if self.peek() == Some(b':') {
+                self.i += 1;
+                s = self.number(Error::Offset)?;
+            }
+        }
+        if h > max_hours || m > 59 || s > 59 {
+            return Err(Error::Offset);
+        }
+        Ok(sign * (h * 3600 + m * 60 + s) as i32)
+    }
+
+    /// Parse a UTC offset, in seconds west of UTC as written in TZ strings.
+    fn offset(&mut self) -> Result<i32, Error> {
+        self.time(24)
+    }
+
+    /// Parse a transition rule and optional `/time`.
+    fn transition(&mut self) -> Result<Transition, Error> {
+        let rule = match self.peek() {
+            Some(b'J') => {
+                self.i += 1;
+                match self.number(Error::Rule)? {
+                    n @ 1..=365 => Rule::Julian1(n as u16),
+                    _ => return Err(Error::Rule),
+                }
+            },
+            Some(b'M') => {
+                self.i += 1;
+                let m = self.number(Error::Rule)?;
+                self.expect(b'.', Error::Rule)?;
+                let w = self.number(Error::Rule)?;
+                self.expect(b'.', Error::Rule)?;
+                let d = self.number(Error::Rule)?;
+                if !(1..=12).contains(&m) || !(1..=5).contains(&w) || d > 6 {
+                    return Err(Error::Rule);
+                }
+                Rule::MonthWeekDay(m as u8, w as u8, d as u8)
+            },
+            _ => match self.number(Error::Rule)? {
+                n @ 0..=365 => Rule::Julian0(n as u16),
+                _ => return Err(Error::Rule),
+            },
+        };
+        let time = if self.peek() == Some(b'/') {
+            self.i += 1;
+            self.time(167)?
+        } else {
+            7200
+        };
+        Ok(Transition { rule, time })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Time;
+
+    fn utc(year: i32, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> PrimitiveDateTime {
+        let date = Date::from_calendar_date(year, Month::try_from(month).unwrap(), day).unwrap();
+        PrimitiveDateTime::new(date, Time::from_hms(hour, minute, second).unwrap())
+    }
+
+    /// Offset in seconds east of UTC for the zone `rule` at `utc`.
+    fn offset(rule: &str, utc: PrimitiveDateTime) -> i32 {
+        TimeZone::parse(rule).unwrap().offset(&utc).whole_seconds()
+    }
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::from_calendar_date(year, Month::try_from(month).unwrap(), day).unwrap()
+    }
+
+    #[test]
+    fn all_zones_parse() {
+        for &name in ZONE_NAMES.iter() {
+            if let Some(rule) = zone_rule(name) {
+                assert!(TimeZone::parse(rule).is_ok(), "{:?}: {}", name, rule);
+            }
+        }
+        assert_eq!(zone_rule(Name::Manual), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases = [
+            ("", Error::Abbreviation),
+            ("GM0", Error::Abbreviation),
+            ("<+1>-1", Error::Abbreviation),
+            ("<+04-4", Error::Abbreviation),
+            ("GMT0B", Error::Abbreviation),
+            ("GMT", Error::Offset),
+            ("GMT25", Error::Offset),
+            ("GMT0:60", Error::Offset),
+            ("GMT0BST,M3.5.0/168,M10.5.0", Error::Offset),
+            ("GMT0BST,M13.1.0,M10.5.0", Error::Rule),
+            ("GMT0BST,M3.6.0,M10.5.0", Error::Rule),
+            ("GMT0BST,M3.5.7,M10.5.0", Error::Rule),
+            ("GMT0BST,M3.5,M10.5.0", Error::Rule),
+            ("GMT0BST,J0,J300", Error::Rule),
+            ("GMT0BST,0,366", Error::Rule),
+            ("GMT0BST,M3.5.0", Error::Rule),
+            ("GMT0BST,M3.5.0,M10.5.0,", Error::Trailing),
+            ("GMT0BST,M3.5.0,M10.5.0 ", Error::Trailing),
+        ];
+        for &(rule, err) in cases.iter() {
+            assert_eq!(TimeZone::parse(rule), Err(err), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let us = TimeZone::parse("EST5EDT,M3.2.0/2,M11.1.0/2:00").unwrap();
+        assert_eq!(TimeZone::parse("EST5EDT"), Ok(us));
+        assert_eq!(TimeZone::parse("EST5EDT4"), Ok(us));
+        assert_eq!(TimeZone::parse("UTC0"), Ok(TimeZone::UTC));
+        assert_eq!(TimeZone::parse("<+0545>-5:45"), Ok(TimeZone { std: 20700, dst: None }));
+    }
+
+    #[test]
+    fn month_week_day_rules() {
+        let cases = [
+            (Rule::MonthWeekDay(3, 5, 0), 2024, date(2024, 3, 31)),
+            (Rule::MonthWeekDay(10, 5, 0), 2024, date(2024, 10, 27)),
+            (Rule::MonthWeekDay(3, 2, 0), 2024, date(2024, 3, 10)),
+            (Rule::MonthWeekDay(11, 1, 0), 2024, date(2024, 11, 3)),
+            (Rule::MonthWeekDay(4, 1, 0), 2024, date(2024, 4, 7)),
+            (Rule::MonthWeekDay(9, 1, 6), 2024, date(2024, 9, 7)),
+            (Rule::MonthWeekDay(2, 5, 4), 2024, date(2024, 2, 29)),
+            (Rule::MonthWeekDay(2, 5, 4), 2023, date(2023, 2, 23)),
+            (Rule::MonthWeekDay(6, 1, 6), 2024, date(2024, 6, 1)),
+        ];
+        for &(rule, year, expected) in cases.iter() {
+            assert_eq!(rule.date(year), expected, "{:?} {}", rule, year);
+        }
+    }
+
+    #[test]
+    fn julian_day_rules() {
+        assert_eq!(Rule::Julian1(1).date(2024), date(2024, 1, 1));
+        assert_eq!(Rule::Julian1(60).date(2023), date(2023, 3, 1));
+        assert_eq!(Rule::Julian1(60).date(2024), date(2024, 3, 1));
+        assert_eq!(Rule::Julian1(365).date(2024), date(2024, 12, 31));
+        assert_eq!(Rule::Julian0(0).date(2024), date(2024, 1, 1));
+        assert_eq!(Rule::Julian0(59).date(2024), date(2024, 2, 29));
+        assert_eq!(Rule::Julian0(365).date(2024), date(2024, 12, 31));
+        assert_eq!(Rule::Julian0(365).date(2023), date(2023, 12, 31));
+    }
+
+    #[test]
+    fn london_transitions() {
+        let london = zone_rule(Name::London).unwrap();
+        assert_eq!(offset(london, utc(2024, 3, 31, 0, 59, 59)), 0);
+        assert_eq!(offset(london, utc(2024, 3, 31, 1, 0, 0)), 3600);
+        assert_eq!(offset(london, utc(2024, 10, 27, 0, 59, 59)), 3600);
+        assert_eq!(offset(london, utc(2024, 10, 27, 1, 0, 0)), 0);
+    }
+
+    #[test]
+    fn new_york_transitions() {
+        let new_york = zone_rule(Name::NewYork).unwrap();
+        assert_eq!(offset(new_york, utc(2024, 3, 10, 6, 59, 59)), -5 * 3600);
+        assert_eq!(offset(new_york, utc(2024, 3, 10, 7, 0, 0)), -4 * 3600);
+        assert_eq!(offset(new_york, utc(2024, 11, 3, 5, 59, 59)), -4 * 3600);
+        assert_eq!(offset(new_york, utc(2024, 11, 3, 6, 0, 0)), -5 * 3600);
+    }
+
+    #[test]
+    fn sydney_transitions() {
+        // DST runs from October to April, across the new year.
+        let sydney = zone_rule(Name::Sydney).unwrap();
+        assert_eq!(offset(sydney, utc(2024, 1, 15, 12, 0, 0)), 11 * 3600);
+        assert_eq!(offset(sydney, utc(2024, 7, 15, 12, 0, 0)), 10 * 3600);
+        // 03:00 AEDT on 7 April.
+        assert_eq!(offset(sydney, utc(2024, 4, 6, 15, 59, 59)), 11 * 3600);
+        assert_eq!(offset(sydney, utc(2024, 4, 6, 16, 0, 0)), 10 * 3600);
+        // 02:00 AEST on 6 October.
+        assert_eq!(offset(sydney, utc(2024, 10, 5, 15, 59, 59)), 10 * 3600);
+        assert_eq!(offset(sydney, utc(2024, 10, 5, 16, 0, 0)), 11 * 3600);
+    }
+
+    #[test]
+    fn santiago_transitions() {
+        // Transitions at 24:00 on the first Saturday, so midnight starting Sunday.
+        let santiago = zone_rule(Name::Santiago).unwrap();
+        assert_eq!(offset(santiago, utc(2024, 1, 15, 12, 0, 0)), -3 * 3600);
+        assert_eq!(offset(santiago, utc(2024, 7, 15, 12, 0, 0)), -4 * 3600);
+        // 24:00 -03 on Saturday 6 April.
+        assert_eq!(offset(santiago, utc(2024, 4, 7, 2, 59, 59)), -3 * 3600);
+        assert_eq!(offset(santiago, utc(2024, 4, 7, 3, 0, 0)), -4 * 3600);
+        // 24:00 -04 on Saturday 7 September.
+        assert_eq!(offset(santiago, utc(2024, 9, 8, 3, 59, 59)), -4 * 3600);
+        assert_eq!(offset(santiago, utc(2024, 9, 8, 4, 0, 0)), -3 * 3600);
+    }
+
+    #[test]
+    fn clock_changes() {
+        let zone = |name| TimeZone::parse(zone_rule(name).unwrap()).unwrap();
+        let london = zone(Name::London);
+        assert_eq!(london.change_on(date(2024, 3, 31)), Some(true));
+        assert_eq!(london.change_on(date(2024, 10, 27)), Some(false));
+        assert_eq!(london.change_on(date(2024, 3, 24)), None);
+        assert_eq!(zone(Name::NewYork).change_on(date(2024, 3, 10)), Some(true));
+        assert_eq!(zone(Name::Sydney).change_on(date(2024, 10, 6)), Some(true));
+        assert_eq!(zone(Name::Sydney).change_on(date(2024, 4, 7)), Some(false));
+        assert_eq!(zone(Name::Santiago).change_on(date(2024, 9, 8)), Some(true));
+        assert_eq!(zone(Name::Santiago).change_on(date(2024, 9, 7)), None);
+        assert_eq!(zone(Name::Japan).change_on(date(2024, 3, 31)), None);
+        assert_eq!(TimeZone::fixed(3600).change_on(date(2024, 3, 31)), None);
+    }
+
+    #[test]
+    fn fractional_hour_offsets() {
+        let india = zone_rule(Name::India).unwrap();
+        assert_eq!(offset(india, utc(2024, 1, 1, 0, 0, 0)), 5 * 3600 + 30 * 60);
+        assert_eq!(offset(india, utc(2024, 7, 1, 0, 0, 0)), 5 * 3600 + 30 * 60);
+
+        let nepal = zone_rule(Name::Nepal).unwrap();
+        assert_eq!(offset(nepal, utc(2024, 1, 1, 0, 0, 0)), 5 * 3600 + 45 * 60);
+
+        let adelaide = zone_rule(Name::Adelaide).unwrap();
+        assert_eq!(offset(adelaide, utc(2024, 1, 15, 0, 0, 0)), 10 * 3600 + 30 * 60);
+        assert_eq!(offset(adelaide, utc(2024, 7, 15, 0, 0, 0)), 9 * 3600 + 30 * 60);
+
+        // 02:00 local on 10 March and 3 November.
+        let newfoundland = zone_rule(Name::Newfoundland).unwrap();
+        let (nst, ndt) = (-(3 * 3600 + 30 * 60), -(2 * 3600 + 30 * 60));
+        assert_eq!(offset(newfoundland, utc(2024, 3, 10, 5, 29, 59)), nst);
+        assert_eq!(offset(newfoundland, utc(2024, 3, 10, 5, 30, 0)), ndt);
+        assert_eq!(offset(newfoundland, utc(2024, 11, 3, 4, 29, 59)), ndt);
+        assert_eq!(offset(newfoundland, utc(2024, 11, 3, 4, 30, 0)), nst);
+    }
+}